@@ -1,17 +1,45 @@
+use chrono::{DateTime, Duration, NaiveDate, Utc};
 use scraper::{Html, Selector};
 
-use super::{ElementRef, ElementRefExt};
+use super::{parse_items, ElementRef, ElementRefExt, NewsId};
 use crate::{NoneErrorExt, Result};
 
-#[derive(Debug, PartialEq)]
+#[derive(Clone, Debug, PartialEq)]
 pub struct ArticleBrief {
     pub name: String,
     pub path: String,
+    /// Raw publication time, like "5 hours ago" or "2020-04-30" (see
+    /// `ArticleBrief::published`).
     pub when: String,
+    /// Raw number of comments, like "29 comments" (see
+    /// `ArticleBrief::comments`).
     pub comments_num: String,
 }
 
 impl ArticleBrief {
+    /// ID of article, parsed from path.
+    pub fn id(&self) -> Result<NewsId> {
+        NewsId::from_link(&self.path)
+    }
+
+    /// Approximate publication time, computed from relative time (like
+    /// "5 hours ago") against `now`. Archived news have only dates, which
+    /// are returned as midnight UTC. Returns `None` if time is not
+    /// recognized.
+    pub fn published(&self, now: DateTime<Utc>) -> Option<DateTime<Utc>> {
+        parse_when(&self.when, now)
+    }
+
+    /// Number of comments, or `None` if it is not recognized.
+    pub fn comments(&self) -> Option<u32> {
+        let mut words = self.comments_num.split_whitespace();
+        let count = words.next()?.parse().ok()?;
+        match words.next() {
+            Some("comment") | Some("comments") | None => Some(count),
+            Some(_) => None,
+        }
+    }
+
     // TODO: doc
     pub fn from_element_ref(element: ElementRef) -> Result<Self> {
         let path = element
@@ -41,7 +69,38 @@ impl ArticleBrief {
     }
 }
 
-#[derive(Debug)]
+/// Parse publication time like "a day ago", "18 hours ago" or "2020-04-30".
+fn parse_when(when: &str, now: DateTime<Utc>) -> Option<DateTime<Utc>> {
+    let when = when.trim();
+    if let Ok(date) = NaiveDate::parse_from_str(when, "%Y-%m-%d") {
+        return Some(date.and_hms_opt(0, 0, 0)?.and_utc());
+    }
+    if when == "just now" || when == "a few seconds ago" {
+        return Some(now);
+    }
+
+    let mut words = when.strip_suffix(" ago")?.split_whitespace();
+    let count = match words.next()? {
+        "a" | "an" => 1,
+        count => count.parse().ok()?,
+    };
+    let unit = match words.next()?.trim_end_matches('s') {
+        "second" => Duration::seconds(1),
+        "minute" => Duration::minutes(1),
+        "hour" => Duration::hours(1),
+        "day" => Duration::days(1),
+        "week" => Duration::weeks(1),
+        "month" => Duration::days(30),
+        "year" => Duration::days(365),
+        _ => return None,
+    };
+    if words.next().is_some() {
+        return None;
+    }
+    now.checked_sub_signed(unit.checked_mul(count)?)
+}
+
+#[derive(Clone, Debug, PartialEq)]
 pub struct MainPageArticleBriefs {
     pub today: Vec<ArticleBrief>,
     pub yesterday: Vec<ArticleBrief>,
@@ -52,16 +111,31 @@ impl MainPageArticleBriefs {
     // TODO: doc
     // TODO: unify code with function `archived_article_briefs_from_html`
     pub fn from_html(document: &Html) -> Result<Self> {
+        enter_span!(
+            "from_html",
+            page = "MainPageArticleBriefs",
+            items_parsed = tracing::field::Empty,
+            parse_failed = tracing::field::Empty,
+        );
+        let res = Self::parse(document);
+        crate::trace::record_parsed(&res, |briefs| {
+            briefs.today.len() + briefs.yesterday.len() + briefs.older.len()
+        });
+        res
+    }
+
+    fn parse(document: &Html) -> Result<Self> {
         let selector = Selector::parse("h2.newsheader+div.standard-box")?;
         let a_selector = Selector::parse("a")?;
 
         let elements = document.select(&selector).collect::<Vec<_>>();
 
         let parse_list = |element: ElementRef| {
-            element
-                .select(&a_selector)
-                .map(ArticleBrief::from_element_ref)
-                .collect::<Result<Vec<ArticleBrief>>>()
+            parse_items(
+                element.select(&a_selector),
+                ArticleBrief::from_element_ref,
+                "news",
+            )
         };
 
         let briefs_blocks = elements
@@ -87,16 +161,29 @@ impl MainPageArticleBriefs {
 }
 
 pub fn archived_article_briefs_from_html(document: &Html) -> Result<Vec<ArticleBrief>> {
+    enter_span!(
+        "from_html",
+        page = "ArchivedArticleBriefs",
+        items_parsed = tracing::field::Empty,
+        parse_failed = tracing::field::Empty,
+    );
+    let res = parse_archived_article_briefs(document);
+    crate::trace::record_parsed(&res, Vec::len);
+    res
+}
+
+fn parse_archived_article_briefs(document: &Html) -> Result<Vec<ArticleBrief>> {
     let selector = Selector::parse("h2.newsheader+div.standard-box")?;
     let a_selector = Selector::parse("a")?;
 
     let elements = document.select(&selector).collect::<Vec<_>>();
 
     let parse_list = |element: ElementRef| {
-        element
-            .select(&a_selector)
-            .map(ArticleBrief::from_element_ref)
-            .collect::<Result<Vec<ArticleBrief>>>()
+        parse_items(
+            element.select(&a_selector),
+            ArticleBrief::from_element_ref,
+            "archived news",
+        )
     };
 
     let briefs_blocks = elements
@@ -114,6 +201,7 @@ pub fn archived_article_briefs_from_html(document: &Html) -> Result<Vec<ArticleB
 mod article_brief_tests {
     use super::*;
 
+    use chrono::TimeZone;
     use scraper::{Html, Selector};
 
     fn get_elem<'a>(html: &'a Html, css_selector: &'_ str) -> ElementRef<'a> {
@@ -147,6 +235,38 @@ mod article_brief_tests {
         )
     }
 
+    #[test]
+    fn published_and_comments() {
+        let now = Utc.with_ymd_and_hms(2021, 2, 13, 12, 0, 0).unwrap();
+        let brief = |when: &str, comments_num: &str| ArticleBrief {
+            name: "".into(),
+            path: "".into(),
+            when: when.into(),
+            comments_num: comments_num.into(),
+        };
+
+        let res = brief("5 hours ago", "29 comments");
+        assert_eq!(res.published(now), Some(now - Duration::hours(5)));
+        assert_eq!(res.comments(), Some(29));
+        let res = brief("a day ago", "1 comment");
+        assert_eq!(res.published(now), Some(now - Duration::days(1)));
+        assert_eq!(res.comments(), Some(1));
+        assert_eq!(
+            brief("2020-04-30", "").published(now),
+            Some(Utc.with_ymd_and_hms(2020, 4, 30, 0, 0, 0).unwrap())
+        );
+        assert_eq!(
+            brief("an hour ago", "").published(now),
+            Some(now - Duration::hours(1))
+        );
+        assert_eq!(brief("a few seconds ago", "").published(now), Some(now));
+
+        let res = brief("yesterday", "many comments");
+        assert_eq!(res.published(now), None);
+        assert_eq!(res.comments(), None);
+        assert_eq!(brief("5 parsecs ago", "").published(now), None);
+    }
+
     #[test]
     fn single_parse_no_path() {
         let html = Html::parse_fragment(
@@ -0,0 +1,247 @@
+use std::time::Instant;
+
+use super::{
+    archived_news_path, cloudflare, matches_results_path, parse_page, EntityId, Month,
+    RequestSettings, MAIN_PAGE_PATH, UPCOMING_MATCHES_PATH,
+};
+use crate::{
+    api::{
+        archived_article_briefs_from_html, ArticleBrief, MainPageArticleBriefs, MatchesResults,
+        UpcomingMatches,
+    },
+    AsyncHttpsClient, Result, HLTV_URL,
+};
+
+/// Asynchronous HLTV API.
+///
+/// Uses the same parsers as `HltvApi`, so both return identical results for
+/// the same pages.
+pub struct AsyncHltvApi {
+    https_client: Box<dyn AsyncHttpsClient>,
+    hltv_root_url: String,
+    settings: RequestSettings,
+}
+
+impl AsyncHltvApi {
+    /// Build new instance of `AsyncHltvApi` with provided HTTPS client and HLTV URL.
+    pub fn new<T: AsyncHttpsClient + 'static, U: Into<String>>(
+        client: T,
+        hltv_root_url: U,
+    ) -> Self {
+        Self {
+            https_client: Box::new(client),
+            hltv_root_url: hltv_root_url.into(),
+            settings: RequestSettings::default(),
+        }
+    }
+
+    /// Build new instance of `AsyncHltvApi` with provided HTTPS client and default HLTV URL.
+    pub fn with_default_path<T: AsyncHttpsClient + 'static>(client: T) -> Self {
+        Self::new(client, HLTV_URL)
+    }
+
+    /// Add header to every request, replacing previous value with the same
+    /// name (including `User-Agent`, set by client).
+    pub fn with_header<N: Into<String>, V: Into<String>>(mut self, name: N, value: V) -> Self {
+        self.settings.header(name.into(), value.into());
+        self
+    }
+
+    /// Add cookie to every request.
+    pub fn with_cookie<N: Into<String>, V: Into<String>>(mut self, name: N, value: V) -> Self {
+        self.settings.cookies.insert(name, value);
+        self
+    }
+
+    /// Get page by path, detecting Cloudflare challenges.
+    async fn get_page(&self, path: &str) -> Result<String> {
+        let request = self
+            .settings
+            .request(format!("{}{}", self.hltv_root_url, path));
+        let res = instrumented!(
+            async {
+                let start = Instant::now();
+                let res = self.https_client.execute(&request).await;
+                crate::trace::record_response(&res, start);
+                res
+            },
+            "http_request",
+            url = %request.url,
+            status = tracing::field::Empty,
+            bytes = tracing::field::Empty,
+            latency_ms = tracing::field::Empty,
+        )
+        .await;
+        cloudflare::check_page(
+            res.map_err(|err| err as Box<dyn std::error::Error>)
+                .and_then(|response| Ok(response.into_body()?)),
+            &request.url,
+        )
+    }
+
+    /// Get news briefs from main page (ie latest news).
+    pub async fn latest_news_briefs(&self) -> Result<MainPageArticleBriefs> {
+        instrumented!(
+            async {
+                parse_page(
+                    &self.get_page(MAIN_PAGE_PATH).await?,
+                    MainPageArticleBriefs::from_html,
+                )
+            },
+            "hltv_api",
+            method = "latest_news_briefs"
+        )
+        .await
+    }
+
+    /// Get news briefs from archive.
+    pub async fn archived_news_briefs(&self, year: u16, month: Month) -> Result<Vec<ArticleBrief>> {
+        instrumented!(
+            async {
+                parse_page(
+                    &self.get_page(&archived_news_path(year, month)).await?,
+                    archived_article_briefs_from_html,
+                )
+            },
+            "hltv_api",
+            method = "archived_news_briefs"
+        )
+        .await
+    }
+
+    /// Get matches results.
+    pub async fn matches_results(&self, page_offset: Option<u64>) -> Result<MatchesResults> {
+        instrumented!(
+            async {
+                parse_page(
+                    &self.get_page(&matches_results_path(page_offset)).await?,
+                    MatchesResults::from_html,
+                )
+            },
+            "hltv_api",
+            method = "matches_results"
+        )
+        .await
+    }
+
+    /// Get upcoming matches.
+    pub async fn upcoming_matches(&self) -> Result<UpcomingMatches> {
+        instrumented!(
+            async {
+                parse_page(
+                    &self.get_page(UPCOMING_MATCHES_PATH).await?,
+                    UpcomingMatches::from_html,
+                )
+            },
+            "hltv_api",
+            method = "upcoming_matches"
+        )
+        .await
+    }
+
+    /// Get raw HTML of page by path (see `HltvApi::raw_page`).
+    pub async fn raw_page(&self, path: &str) -> Result<String> {
+        instrumented!(self.get_page(path), "hltv_api", method = "raw_page").await
+    }
+
+    /// Get raw HTML of entity page (see `HltvApi::entity_page`).
+    pub async fn entity_page<I: EntityId>(&self, id: &I) -> Result<String> {
+        let path = id.path();
+        instrumented!(self.get_page(&path), "hltv_api", method = "entity_page").await
+    }
+
+    /// Get raw HTML of main page.
+    pub async fn latest_news_page(&self) -> Result<String> {
+        self.raw_page(MAIN_PAGE_PATH).await
+    }
+
+    /// Get raw HTML of news archive page.
+    pub async fn archived_news_page(&self, year: u16, month: Month) -> Result<String> {
+        self.raw_page(&archived_news_path(year, month)).await
+    }
+
+    /// Get raw HTML of matches results page.
+    pub async fn matches_results_page(&self, page_offset: Option<u64>) -> Result<String> {
+        self.raw_page(&matches_results_path(page_offset)).await
+    }
+
+    /// Get raw HTML of upcoming matches page.
+    pub async fn upcoming_matches_page(&self) -> Result<String> {
+        self.raw_page(UPCOMING_MATCHES_PATH).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::error::Error as StdError;
+
+    use super::*;
+    use crate::{Error, HltvApi, HttpsClient};
+
+    const ARCHIVE_PAGE: &str = r#"
+<div class="index">
+  <h2 class="newsheader">November 2020</h2>
+  <div class="standard-box standard-list">
+    <a href="/news/30594/flashpoint-2-fantasy-game-live-with-prizes" class="newsline article">
+      <div class="newstext">Flashpoint 2 Fantasy game live with prizes</div>
+      <div class="newstc">
+        <div class="newsrecent">2020-11-08</div>
+        <div>29 comments</div>
+      </div>
+    </a>
+  </div>
+</div>
+"#;
+
+    struct StaticClient;
+
+    impl HttpsClient for StaticClient {
+        fn get(&self, _url: &str) -> std::result::Result<String, Box<dyn StdError>> {
+            Ok(ARCHIVE_PAGE.into())
+        }
+    }
+
+    #[async_trait::async_trait]
+    impl AsyncHttpsClient for StaticClient {
+        async fn get(
+            &self,
+            url: &str,
+        ) -> std::result::Result<String, Box<dyn StdError + Send + Sync>> {
+            if url == "https://www.hltv.org/news/archive/2020/november" {
+                Ok(ARCHIVE_PAGE.into())
+            } else {
+                Err(format!("Unexpected URL {}", url).into())
+            }
+        }
+    }
+
+    #[test]
+    fn archived_news_briefs_same_as_sync() {
+        let async_res = futures::executor::block_on(
+            AsyncHltvApi::with_default_path(StaticClient)
+                .archived_news_briefs(2020, Month::November),
+        );
+        let sync_res =
+            HltvApi::with_default_path(StaticClient).archived_news_briefs(2020, Month::November);
+
+        assert_eq!(async_res, sync_res);
+        assert_eq!(async_res.unwrap().len(), 1);
+    }
+
+    #[test]
+    fn client_error() {
+        let res = futures::executor::block_on(
+            AsyncHltvApi::with_default_path(StaticClient).matches_results(None),
+        );
+
+        assert!(matches!(res, Err(Error::Transport { .. })));
+    }
+
+    #[test]
+    fn raw_page() {
+        let api = AsyncHltvApi::with_default_path(StaticClient);
+        let html =
+            futures::executor::block_on(api.archived_news_page(2020, Month::November)).unwrap();
+        assert_eq!(html, ARCHIVE_PAGE);
+    }
+}
@@ -0,0 +1,335 @@
+use std::{path::PathBuf, time::Duration};
+
+use super::{parsed_cache::ParsedCache, HltvApi, ParsedCacheConfig, RequestSettings};
+#[cfg(feature = "attohttpc_client")]
+use crate::{AttoHttpcConfig, AttoHttpcImpl};
+use crate::{
+    CacheTtl, CachingClient, CircuitBreakerClient, CircuitBreakerConfig, Error, HttpsClient,
+    Result, RetryClient, RetryPolicy, ThrottleConfig, ThrottledClient, HLTV_URL,
+};
+
+type BoxedClient = Box<dyn HttpsClient + Send + Sync>;
+
+/// Builder of `HltvApi` (see `HltvApi::builder`).
+///
+/// Provided client (or `AttoHttpcImpl`, if client is not set) is wrapped,
+/// from inner to outer, in `ThrottledClient`, `CircuitBreakerClient`,
+/// `RetryClient` and `CachingClient`, so retries are throttled and cached
+/// pages do not spend request budget. Only configured wrappers are added.
+pub struct HltvApiBuilder {
+    client: Option<BoxedClient>,
+    root_url: String,
+    user_agent: Option<String>,
+    connect_timeout: Option<Duration>,
+    read_timeout: Option<Duration>,
+    max_body_size: Option<Option<u64>>,
+    max_redirects: Option<u32>,
+    throttle: Option<ThrottleConfig>,
+    circuit_breaker: Option<CircuitBreakerConfig>,
+    retry: Option<RetryPolicy>,
+    cache_dir: Option<PathBuf>,
+    cache_default_ttl: CacheTtl,
+    cache_rules: Vec<(String, CacheTtl)>,
+    parsed_cache: Option<ParsedCacheConfig>,
+    detect_blocks: bool,
+}
+
+impl Default for HltvApiBuilder {
+    fn default() -> Self {
+        Self {
+            client: None,
+            root_url: HLTV_URL.into(),
+            user_agent: None,
+            connect_timeout: None,
+            read_timeout: None,
+            max_body_size: None,
+            max_redirects: None,
+            throttle: None,
+            circuit_breaker: None,
+            retry: None,
+            cache_dir: None,
+            cache_default_ttl: CacheTtl::NoCache,
+            cache_rules: Vec::new(),
+            parsed_cache: None,
+            detect_blocks: true,
+        }
+    }
+}
+
+impl HltvApiBuilder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Use provided client instead of `AttoHttpcImpl`. Required, if
+    /// `attohttpc_client` feature is disabled.
+    pub fn client<C: HttpsClient + Send + Sync + 'static>(mut self, client: C) -> Self {
+        self.client = Some(Box::new(client));
+        self
+    }
+
+    /// Set HLTV URL (`HLTV_URL` by default).
+    pub fn root_url<U: Into<String>>(mut self, root_url: U) -> Self {
+        self.root_url = root_url.into();
+        self
+    }
+
+    /// Set `User-Agent` header of every request.
+    pub fn user_agent<U: Into<String>>(mut self, user_agent: U) -> Self {
+        self.user_agent = Some(user_agent.into());
+        self
+    }
+
+    /// Set timeout for establishing connection. Can be set only for default
+    /// client, configure provided client directly.
+    pub fn connect_timeout(mut self, timeout: Duration) -> Self {
+        self.connect_timeout = Some(timeout);
+        self
+    }
+
+    /// Set timeout for reading from socket. Can be set only for default
+    /// client, configure provided client directly.
+    pub fn read_timeout(mut self, timeout: Duration) -> Self {
+        self.read_timeout = Some(timeout);
+        self
+    }
+
+    /// Set maximal size of response body (`None` means unlimited). Can be
+    /// set only for default client.
+    pub fn max_body_size(mut self, limit: Option<u64>) -> Self {
+        self.max_body_size = Some(limit);
+        self
+    }
+
+    /// Set maximal number of followed redirects. Can be set only for default
+    /// client.
+    pub fn max_redirects(mut self, limit: u32) -> Self {
+        self.max_redirects = Some(limit);
+        self
+    }
+
+    /// Limit rate of requests (see `ThrottledClient`).
+    pub fn throttle(mut self, config: ThrottleConfig) -> Self {
+        self.throttle = Some(config);
+        self
+    }
+
+    /// Stop sending requests after consecutive failures (see
+    /// `CircuitBreakerClient`).
+    pub fn circuit_breaker(mut self, config: CircuitBreakerConfig) -> Self {
+        self.circuit_breaker = Some(config);
+        self
+    }
+
+    /// Retry failed requests (see `RetryClient`).
+    pub fn retry(mut self, policy: RetryPolicy) -> Self {
+        self.retry = Some(policy);
+        self
+    }
+
+    /// Cache responses on disk in provided directory with default TTL (see
+    /// `CachingClient`).
+    pub fn cache<P: Into<PathBuf>>(mut self, dir: P, default_ttl: CacheTtl) -> Self {
+        self.cache_dir = Some(dir.into());
+        self.cache_default_ttl = default_ttl;
+        self
+    }
+
+    /// Set TTL of cached responses for URLs, which path starts with
+    /// `path_prefix` (see `CachingClient::with_ttl`). Requires `cache`.
+    pub fn cache_ttl<P: Into<String>>(mut self, path_prefix: P, ttl: CacheTtl) -> Self {
+        self.cache_rules.push((path_prefix.into(), ttl));
+        self
+    }
+
+    /// Cache parsed values in memory (see `HltvApi::with_parsed_cache`).
+    pub fn parsed_cache(mut self, config: ParsedCacheConfig) -> Self {
+        self.parsed_cache = Some(config);
+        self
+    }
+
+    /// Enable or disable detection of Cloudflare challenge pages (enabled by
+    /// default). If disabled, such pages are passed to parsers.
+    pub fn detect_blocks(mut self, detect_blocks: bool) -> Self {
+        self.detect_blocks = detect_blocks;
+        self
+    }
+
+    /// Build `HltvApi`. Fails if configuration is inconsistent or cache
+    /// directory cannot be created.
+    pub fn build(mut self) -> Result<HltvApi> {
+        let mut client = self.base_client()?;
+        if let Some(config) = self.throttle {
+            client = Box::new(ThrottledClient::with_config(client, config));
+        }
+        if let Some(config) = self.circuit_breaker {
+            client = Box::new(CircuitBreakerClient::with_config(client, config));
+        }
+        if let Some(policy) = self.retry {
+            client = Box::new(RetryClient::with_policy(client, policy));
+        }
+        match self.cache_dir {
+            Some(dir) => {
+                let cache = self.cache_rules.into_iter().fold(
+                    CachingClient::new(client, dir)?.with_default_ttl(self.cache_default_ttl),
+                    |cache, (path_prefix, ttl)| cache.with_ttl(path_prefix, ttl),
+                );
+                client = Box::new(cache);
+            }
+            None if !self.cache_rules.is_empty() => {
+                return Err(invalid_config("cache TTL is set without cache directory"))
+            }
+            None => {}
+        }
+
+        let mut settings = RequestSettings::default();
+        if let Some(user_agent) = self.user_agent {
+            settings.header("User-Agent".into(), user_agent);
+        }
+        Ok(HltvApi {
+            https_client: client,
+            hltv_root_url: self.root_url,
+            settings,
+            last_parsed: ParsedCache::new(self.parsed_cache.clone().unwrap_or_default()),
+            parsed_cache: self.parsed_cache.map(ParsedCache::new),
+            detect_blocks: self.detect_blocks,
+        })
+    }
+
+    #[cfg(feature = "attohttpc_client")]
+    fn base_client(&mut self) -> Result<BoxedClient> {
+        if let Some(client) = self.client.take() {
+            return self.check_no_client_settings().map(|_| client);
+        }
+        let default = AttoHttpcConfig::default();
+        Ok(Box::new(AttoHttpcImpl::with_config(AttoHttpcConfig {
+            connect_timeout: self.connect_timeout.unwrap_or(default.connect_timeout),
+            read_timeout: self.read_timeout.unwrap_or(default.read_timeout),
+            max_body_size: self.max_body_size.unwrap_or(default.max_body_size),
+            max_redirects: self.max_redirects.unwrap_or(default.max_redirects),
+        })))
+    }
+
+    #[cfg(not(feature = "attohttpc_client"))]
+    fn base_client(&mut self) -> Result<BoxedClient> {
+        let client = self
+            .client
+            .take()
+            .ok_or_else(|| invalid_config("client is not set"))?;
+        self.check_no_client_settings().map(|_| client)
+    }
+
+    fn check_no_client_settings(&self) -> Result<()> {
+        if self.connect_timeout.is_some()
+            || self.read_timeout.is_some()
+            || self.max_body_size.is_some()
+            || self.max_redirects.is_some()
+        {
+            Err(invalid_config(
+                "timeouts and limits can be set only for default client",
+            ))
+        } else {
+            Ok(())
+        }
+    }
+}
+
+fn invalid_config(message: &str) -> Error {
+    Error::InvalidConfig(message.into())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        error::Error as StdError,
+        sync::{
+            atomic::{AtomicUsize, Ordering},
+            Arc,
+        },
+    };
+
+    use super::*;
+    use crate::{Request, Response};
+
+    const CHALLENGE: &str = "<title>Just a moment...</title>";
+
+    /// Client, which counts requests and echoes `User-Agent` header.
+    struct EchoClient(Arc<AtomicUsize>);
+
+    impl HttpsClient for EchoClient {
+        fn get(&self, url: &str) -> std::result::Result<String, Box<dyn StdError>> {
+            crate::get_via_execute(self, url)
+        }
+
+        fn execute(&self, request: &Request) -> std::result::Result<Response, Box<dyn StdError>> {
+            self.0.fetch_add(1, Ordering::SeqCst);
+            let body = match request.url.as_str() {
+                "http://hltv.test/challenge" => CHALLENGE,
+                _ => request.header("User-Agent").unwrap_or_default(),
+            };
+            Ok(Response::ok(request.url.as_str(), body))
+        }
+    }
+
+    #[test]
+    fn build() {
+        let counter = Arc::new(AtomicUsize::new(0));
+        let dir = tempfile::tempdir().unwrap();
+        let api = HltvApi::builder()
+            .client(EchoClient(counter.clone()))
+            .root_url("http://hltv.test")
+            .user_agent("test")
+            .throttle(ThrottleConfig::default())
+            .circuit_breaker(CircuitBreakerConfig::default())
+            .retry(RetryPolicy::default())
+            .cache(dir.path(), CacheTtl::Forever)
+            .build()
+            .unwrap();
+
+        assert_eq!(api.get_page("/").unwrap(), "test");
+        assert_eq!(api.get_page("/").unwrap(), "test");
+        assert_eq!(counter.load(Ordering::SeqCst), 1);
+
+        // Challenge pages are not cached
+        for _ in 0..2 {
+            assert!(matches!(
+                api.get_page("/challenge"),
+                Err(Error::Blocked { .. })
+            ));
+        }
+        assert_eq!(counter.load(Ordering::SeqCst), 3);
+    }
+
+    #[test]
+    fn detect_blocks() {
+        let api = HltvApi::builder()
+            .client(EchoClient(Arc::new(AtomicUsize::new(0))))
+            .root_url("http://hltv.test")
+            .detect_blocks(false)
+            .build()
+            .unwrap();
+        assert_eq!(api.get_page("/challenge").unwrap(), CHALLENGE);
+    }
+
+    #[test]
+    fn invalid_configuration() {
+        let client = || EchoClient(Arc::new(AtomicUsize::new(0)));
+        let invalid = |builder: HltvApiBuilder| match builder.build() {
+            Err(err) => {
+                assert!(!err.is_retryable());
+                matches!(err, Error::InvalidConfig(_))
+            }
+            Ok(_) => false,
+        };
+        assert!(invalid(
+            HltvApi::builder()
+                .client(client())
+                .read_timeout(Duration::from_secs(1))
+        ));
+        assert!(invalid(
+            HltvApi::builder()
+                .client(client())
+                .cache_ttl("/", CacheTtl::Forever)
+        ));
+    }
+}
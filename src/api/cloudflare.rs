@@ -0,0 +1,92 @@
+use std::error::Error as StdError;
+
+use crate::{detect_block, Error, HttpStatusError, Result};
+
+/// Convert result of `HttpsClient::get` for `url` to page, detecting
+/// Cloudflare challenges both in successful responses and in error statuses.
+pub(crate) fn check_page(
+    res: std::result::Result<String, Box<dyn StdError>>,
+    url: &str,
+) -> Result<String> {
+    match res {
+        Ok(page) => match detect_block(&page) {
+            Some(kind) => Err(Error::Blocked {
+                kind,
+                retry_after: None,
+            }),
+            None => Ok(page),
+        },
+        Err(err) => Err(blocked_error(err.as_ref())
+            .unwrap_or_else(|| Error::from_client_error(err.as_ref(), url))),
+    }
+}
+
+/// Build `Error::Blocked` from error status with Cloudflare page.
+fn blocked_error(err: &(dyn StdError + 'static)) -> Option<Error> {
+    let err = err.downcast_ref::<HttpStatusError>()?;
+    let kind = detect_block(&err.body)?;
+    Some(Error::Blocked {
+        kind,
+        retry_after: err.retry_after,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::*;
+    use crate::BlockKind;
+
+    const URL: &str = "https://www.hltv.org/";
+
+    const JS_CHALLENGE: &str = "<html><head><title>Just a moment...</title></head></html>";
+
+    const CAPTCHA: &str = r#"<html><body><div class="cf-captcha-container"></div></body></html>"#;
+
+    #[test]
+    fn check_ok_page() {
+        assert_eq!(
+            check_page(Ok("<html></html>".into()), URL),
+            Ok("<html></html>".into())
+        );
+        assert_eq!(
+            check_page(Ok(JS_CHALLENGE.into()), URL),
+            Err(Error::Blocked {
+                kind: BlockKind::JsChallenge,
+                retry_after: None
+            })
+        );
+    }
+
+    #[test]
+    fn check_status_error() {
+        let err = |status, body: &str| {
+            Err(Box::new(HttpStatusError {
+                status,
+                url: URL.into(),
+                retry_after: Some(Duration::from_secs(30)),
+                body: body.into(),
+            }) as Box<dyn StdError>)
+        };
+
+        assert!(matches!(
+            check_page(err(503, JS_CHALLENGE), URL),
+            Err(Error::Blocked {
+                kind: BlockKind::JsChallenge,
+                retry_after: Some(_)
+            })
+        ));
+        assert!(matches!(
+            check_page(err(403, CAPTCHA), URL),
+            Err(Error::Blocked {
+                kind: BlockKind::Captcha,
+                ..
+            })
+        ));
+        assert!(matches!(
+            check_page(err(404, "Not found"), URL),
+            Err(Error::HttpStatus { status: 404, .. })
+        ));
+    }
+}
@@ -0,0 +1,176 @@
+use std::{fmt, str::FromStr};
+
+use crate::{Error, Result, HLTV_URL};
+
+/// ID of HLTV entity (match, news, event, team or player), which has its own
+/// page like `/matches/2345169/stmn-vs-loto-...`.
+pub trait EntityId {
+    /// First segment of entity page path, like `matches`.
+    const PATH_PREFIX: &'static str;
+
+    fn id(&self) -> u64;
+
+    /// Human-readable part of path, if known.
+    fn slug(&self) -> Option<&str>;
+
+    /// Path of entity page. Without slug path is `/<prefix>/<id>`, which
+    /// HLTV redirects to canonical one.
+    fn path(&self) -> String {
+        match self.slug() {
+            Some(slug) => format!("/{}/{}/{}", Self::PATH_PREFIX, self.id(), slug),
+            None => format!("/{}/{}", Self::PATH_PREFIX, self.id()),
+        }
+    }
+
+    /// Canonical URL of entity page on `HLTV_URL`.
+    fn url(&self) -> String {
+        format!("{}{}", HLTV_URL, self.path())
+    }
+}
+
+/// Parse ID and slug from path or URL like
+/// `https://www.hltv.org/matches/2345169/stmn-vs-loto`.
+fn parse_link(link: &str, prefix: &str) -> Result<(u64, Option<String>)> {
+    let invalid = || Error::HltvParse(format!("Invalid {} link '{}'", prefix, link));
+
+    let path = link.trim();
+    let path = match path.split_once("://") {
+        Some((_, rest)) => rest.find('/').map_or("", |idx| &rest[idx..]),
+        None => path,
+    };
+    let path = path.split(['?', '#']).next().unwrap_or_default();
+    let mut segments = path.trim_start_matches('/').split('/');
+    if segments.next() != Some(prefix) {
+        return Err(invalid());
+    }
+    let id = segments
+        .next()
+        .and_then(|id| id.parse().ok())
+        .ok_or_else(invalid)?;
+    let slug = segments.next().filter(|slug| !slug.is_empty());
+    if segments.any(|segment| !segment.is_empty()) {
+        return Err(invalid());
+    }
+    Ok((id, slug.map(Into::into)))
+}
+
+macro_rules! entity_id {
+    ($(#[$meta:meta])* $name:ident, $prefix:literal) => {
+        $(#[$meta])*
+        #[derive(Clone, Debug, PartialEq, Eq, Hash)]
+        pub struct $name {
+            pub id: u64,
+            pub slug: Option<String>,
+        }
+
+        impl $name {
+            pub fn new(id: u64) -> Self {
+                Self { id, slug: None }
+            }
+
+            pub fn with_slug<S: Into<String>>(id: u64, slug: S) -> Self {
+                Self {
+                    id,
+                    slug: Some(slug.into()),
+                }
+            }
+
+            /// Parse ID from path or URL of entity page.
+            pub fn from_link(link: &str) -> Result<Self> {
+                parse_link(link, $prefix).map(|(id, slug)| Self { id, slug })
+            }
+        }
+
+        impl EntityId for $name {
+            const PATH_PREFIX: &'static str = $prefix;
+
+            fn id(&self) -> u64 {
+                self.id
+            }
+
+            fn slug(&self) -> Option<&str> {
+                self.slug.as_deref()
+            }
+        }
+
+        impl FromStr for $name {
+            type Err = Error;
+
+            fn from_str(link: &str) -> Result<Self> {
+                Self::from_link(link)
+            }
+        }
+
+        /// Formatted as page path.
+        impl fmt::Display for $name {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                f.write_str(&self.path())
+            }
+        }
+    };
+}
+
+entity_id!(
+    /// ID of match, like in `/matches/2345169/stmn-vs-loto-...`.
+    MatchId,
+    "matches"
+);
+entity_id!(
+    /// ID of news article, like in `/news/30594/flashpoint-2-...`.
+    NewsId,
+    "news"
+);
+entity_id!(
+    /// ID of event, like in `/events/5620/fireleague-latin-power-...`.
+    EventId,
+    "events"
+);
+entity_id!(
+    /// ID of team, like in `/team/4411/nip`.
+    TeamId,
+    "team"
+);
+entity_id!(
+    /// ID of player, like in `/player/7998/s1mple`.
+    PlayerId,
+    "player"
+);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse() {
+        let id = MatchId::from_link(
+            "/matches/2345169/stmn-vs-loto-fireleague-latin-power-blast-premier-qualifier",
+        )
+        .unwrap();
+        assert_eq!(
+            id,
+            MatchId::with_slug(
+                2345169,
+                "stmn-vs-loto-fireleague-latin-power-blast-premier-qualifier"
+            )
+        );
+        assert_eq!(
+            id.url(),
+            "https://www.hltv.org/matches/2345169/stmn-vs-loto-fireleague-latin-power-blast-premier-qualifier"
+        );
+
+        assert_eq!(
+            "https://www.hltv.org/team/4411/nip?tab=matches".parse(),
+            Ok(TeamId::with_slug(4411, "nip"))
+        );
+        assert_eq!(NewsId::from_link("/news/30594/"), Ok(NewsId::new(30594)));
+        assert_eq!(PlayerId::new(7998).path(), "/player/7998");
+        assert_eq!(
+            EventId::with_slug(5620, "fireleague").to_string(),
+            "/events/5620/fireleague"
+        );
+
+        assert!(MatchId::from_link("/news/30594/flashpoint").is_err());
+        assert!(MatchId::from_link("/matches/live").is_err());
+        assert!(MatchId::from_link("/matches/1/slug/extra").is_err());
+    }
+}
@@ -0,0 +1,138 @@
+/// Competitive map.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum Map {
+    Dust2,
+    Mirage,
+    Inferno,
+    Nuke,
+    Overpass,
+    Vertigo,
+    Ancient,
+    Anubis,
+    Train,
+    Cache,
+    Cobblestone,
+    Tuscany,
+    Season,
+}
+
+impl Map {
+    const ALL: [Map; 13] = [
+        Self::Dust2,
+        Self::Mirage,
+        Self::Inferno,
+        Self::Nuke,
+        Self::Overpass,
+        Self::Vertigo,
+        Self::Ancient,
+        Self::Anubis,
+        Self::Train,
+        Self::Cache,
+        Self::Cobblestone,
+        Self::Tuscany,
+        Self::Season,
+    ];
+
+    /// Parse short map code, used by HLTV (like `d2` or `mrg`), or map name
+    /// (like `Dust2`), ignoring case.
+    pub fn from_code(code: &str) -> Option<Self> {
+        Self::ALL.iter().copied().find(|map| {
+            map.code().eq_ignore_ascii_case(code) || map.name().eq_ignore_ascii_case(code)
+        })
+    }
+
+    /// Short map code, used by HLTV.
+    pub fn code(self) -> &'static str {
+        match self {
+            Self::Dust2 => "d2",
+            Self::Mirage => "mrg",
+            Self::Inferno => "inf",
+            Self::Nuke => "nuke",
+            Self::Overpass => "ovp",
+            Self::Vertigo => "vtg",
+            Self::Ancient => "anc",
+            Self::Anubis => "anb",
+            Self::Train => "trn",
+            Self::Cache => "cch",
+            Self::Cobblestone => "cbl",
+            Self::Tuscany => "tcn",
+            Self::Season => "ssn",
+        }
+    }
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::Dust2 => "Dust2",
+            Self::Mirage => "Mirage",
+            Self::Inferno => "Inferno",
+            Self::Nuke => "Nuke",
+            Self::Overpass => "Overpass",
+            Self::Vertigo => "Vertigo",
+            Self::Ancient => "Ancient",
+            Self::Anubis => "Anubis",
+            Self::Train => "Train",
+            Self::Cache => "Cache",
+            Self::Cobblestone => "Cobblestone",
+            Self::Tuscany => "Tuscany",
+            Self::Season => "Season",
+        }
+    }
+}
+
+/// Format of match, like best of 3 or single map.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub enum MatchFormat {
+    /// Best of `n` maps.
+    BestOf(u8),
+    /// Single map.
+    Map(Map),
+    /// Unrecognized format text.
+    Unknown(String),
+}
+
+impl MatchFormat {
+    /// Parse format text like `bo3` or `d2`.
+    pub fn from_text(text: &str) -> Self {
+        let text = text.trim();
+        let best_of = text
+            .get(..2)
+            .filter(|prefix| prefix.eq_ignore_ascii_case("bo"))
+            .and_then(|_| text[2..].parse().ok());
+        if let Some(maps) = best_of {
+            return Self::BestOf(maps);
+        }
+        match Map::from_code(text) {
+            Some(map) => Self::Map(map),
+            None => Self::Unknown(text.into()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse() {
+        assert_eq!(MatchFormat::from_text("bo3"), MatchFormat::BestOf(3));
+        assert_eq!(MatchFormat::from_text(" BO1 "), MatchFormat::BestOf(1));
+        assert_eq!(MatchFormat::from_text("d2"), MatchFormat::Map(Map::Dust2));
+        assert_eq!(MatchFormat::from_text("anb"), MatchFormat::Map(Map::Anubis));
+        assert_eq!(
+            MatchFormat::from_text("Mirage"),
+            MatchFormat::Map(Map::Mirage)
+        );
+        assert_eq!(
+            MatchFormat::from_text("def"),
+            MatchFormat::Unknown("def".into())
+        );
+        assert_eq!(
+            MatchFormat::from_text("box"),
+            MatchFormat::Unknown("box".into())
+        );
+
+        for map in Map::ALL.iter() {
+            assert_eq!(Map::from_code(map.code()), Some(*map));
+        }
+    }
+}
@@ -1,5 +1,33 @@
+pub use format::*;
 pub use results::*;
 pub use upcoming::*;
 
+use chrono::{DateTime, TimeZone, Utc};
+
+use crate::{api::ElementRef, Error, Result};
+
+mod format;
 mod results;
 mod upcoming;
+
+/// Parse time of match from `data-zonedgrouping-entry-unix` attribute
+/// (milliseconds since epoch), if element has it.
+fn entry_time(element: ElementRef) -> Result<Option<DateTime<Utc>>> {
+    unix_time_attr(element, "data-zonedgrouping-entry-unix")
+}
+
+/// Parse time from attribute with milliseconds since epoch, if element has
+/// it.
+fn unix_time_attr(element: ElementRef, attr: &str) -> Result<Option<DateTime<Utc>>> {
+    let value = match element.value().attr(attr) {
+        Some(value) => value,
+        None => return Ok(None),
+    };
+    value
+        .trim()
+        .parse::<i64>()
+        .ok()
+        .and_then(|millis| Utc.timestamp_millis_opt(millis).single())
+        .map(Some)
+        .ok_or_else(|| Error::HltvParse(format!("Invalid match time '{}'", value)))
+}
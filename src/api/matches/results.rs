@@ -1,25 +1,35 @@
 use std::collections::HashMap;
 
+use chrono::{DateTime, NaiveDate, Utc};
 use scraper::{Html, Selector};
 
+use super::{entry_time, MatchFormat};
 use crate::{
-    api::{ElementRef, ElementRefExt},
+    api::{parse_items, ElementRef, ElementRefExt, MatchId, Month},
     Error, NoneErrorExt, Result,
 };
 
 /// Short match result.
-#[derive(Debug, PartialEq)]
+#[derive(Clone, Debug, PartialEq)]
 pub struct MatchResult {
     pub team1: String,
     pub team2: String,
     pub result: (u8, u8),
     pub link: String,
     pub event: String,
-    pub map: String,
+    /// Format of match or single played map.
+    pub format: MatchFormat,
     pub stars: usize,
+    /// Time of match, if known.
+    pub time: Option<DateTime<Utc>>,
 }
 
 impl MatchResult {
+    /// ID of match, parsed from link.
+    pub fn id(&self) -> Result<MatchId> {
+        MatchId::from_link(&self.link)
+    }
+
     // TODO: doc
     pub fn from_element_ref(element: ElementRef) -> Result<Self> {
         let link = element
@@ -59,33 +69,75 @@ impl MatchResult {
             .hltv_parse_err("Failed to find event name for match result")?
             .text2();
 
-        let map = element
-            .select_one("div.map-text")?
-            .hltv_parse_err("Failed to find map for match result")?
-            .text2();
+        let format = MatchFormat::from_text(
+            &element
+                .select_one("div.map-text")?
+                .hltv_parse_err("Failed to find map for match result")?
+                .text2(),
+        );
 
         let stars = element.select(&Selector::parse("i.star")?).count();
 
+        let time = entry_time(element)?;
+
         Ok(Self {
             team1,
             team2,
             result: (team1_score, team2_score),
             link,
             event,
-            map,
+            format,
             stars,
+            time,
         })
     }
 }
 
+/// Parse date from day headline like "Results for November 8th 2020".
+fn parse_day_date(headline: &str) -> Result<NaiveDate> {
+    let invalid = || Error::HltvParse(format!("Invalid results day '{}'", headline));
+
+    let date = headline.trim();
+    let date = date.strip_prefix("Results for").unwrap_or(date);
+    let mut parts = date.split_whitespace();
+    let month = parts
+        .next()
+        .and_then(Month::from_name)
+        .ok_or_else(invalid)?;
+    let day = parts
+        .next()
+        .map(|day| day.trim_end_matches(|c: char| c.is_ascii_alphabetic()))
+        .and_then(|day| day.parse().ok())
+        .ok_or_else(invalid)?;
+    let year = parts
+        .next()
+        .and_then(|year| year.parse().ok())
+        .ok_or_else(invalid)?;
+    NaiveDate::from_ymd_opt(year, month.number(), day).ok_or_else(invalid)
+}
+
 /// Short batch results for multiple days.
+#[derive(Clone, Debug, PartialEq)]
 pub struct MatchesResults {
-    pub results: HashMap<String, Vec<MatchResult>>,
+    /// Results by day.
+    pub results: HashMap<NaiveDate, Vec<MatchResult>>,
 }
 
 impl MatchesResults {
     // TODO: doc
     pub fn from_html(document: &Html) -> Result<Self> {
+        enter_span!(
+            "from_html",
+            page = "MatchesResults",
+            items_parsed = tracing::field::Empty,
+            parse_failed = tracing::field::Empty,
+        );
+        let res = Self::parse(document);
+        crate::trace::record_parsed(&res, |parsed| parsed.results.values().map(Vec::len).sum());
+        res
+    }
+
+    fn parse(document: &Html) -> Result<Self> {
         document
             .select(&Selector::parse(
                 "div.results-holder>div.results-all>div.results-sublist",
@@ -96,20 +148,24 @@ impl MatchesResults {
                     .hltv_parse_err("Failed to find day headline")?
                     .text2();
 
-                let results = element
-                    .select(&Selector::parse("div.result-con")?)
-                    .map(|x| MatchResult::from_element_ref(x))
-                    .collect::<Result<Vec<_>>>()?;
-                Ok((day, results))
+                let results = parse_items(
+                    element.select(&Selector::parse("div.result-con")?),
+                    MatchResult::from_element_ref,
+                    &day,
+                )?;
+                Ok((parse_day_date(&day)?, results))
             })
             .collect::<Result<HashMap<_, _>>>()
-            .map(|results| MatchesResults { results })
+            .map(|results| Self { results })
     }
 }
 
 #[cfg(test)]
 mod tests {
+    use chrono::TimeZone;
+
     use super::*;
+    use crate::api::Map;
 
     use scraper::{Html, Selector};
 
@@ -159,12 +215,31 @@ ttps://img-cdn.hltv.org/teamlogo/Mzhpx1A4I2IitU0VrUJWuO.png?ixlib=java-2.1.0&amp
                     "/matches/2345169/stmn-vs-loto-fireleague-latin-power-blast-premier-qualifier"
                         .into(),
                 event: "FiReLEAGUE Latin Power - BLAST Premier Qualifier".into(),
-                map: "bo3".into(),
+                format: MatchFormat::BestOf(3),
                 stars: 0,
+                time: Some(Utc.timestamp_millis_opt(1604796087000).unwrap()),
             })
         )
     }
 
+    #[test]
+    fn parse_day_dates() {
+        assert_eq!(
+            parse_day_date("Results for November 8th 2020"),
+            Ok(NaiveDate::from_ymd_opt(2020, 11, 8).unwrap())
+        );
+        assert_eq!(
+            parse_day_date("Results for May 1st 2019"),
+            Ok(NaiveDate::from_ymd_opt(2019, 5, 1).unwrap())
+        );
+        assert_eq!(
+            parse_day_date("August 22nd 2021"),
+            Ok(NaiveDate::from_ymd_opt(2021, 8, 22).unwrap())
+        );
+        assert!(parse_day_date("Results for Smarch 1st 2020").is_err());
+        assert!(parse_day_date("Results for February 30th 2020").is_err());
+    }
+
     #[test]
     fn parse_match_result_no_scores() {
         let html = Html::parse_fragment(
@@ -352,9 +427,16 @@ ttps://img-cdn.hltv.org/teamlogo/Mzhpx1A4I2IitU0VrUJWuO.png?ixlib=java-2.1.0&amp
         );
 
         let res = MatchesResults::from_html(&html).unwrap();
+        let nov7 = NaiveDate::from_ymd_opt(2020, 11, 7).unwrap();
+        let nov8 = NaiveDate::from_ymd_opt(2020, 11, 8).unwrap();
         assert_eq!(res.results.len(), 2);
-        assert_eq!(res.results["Results for November 7th 2020"].len(), 3);
-        assert_eq!(res.results["Results for November 8th 2020"].len(), 2);
+        assert_eq!(res.results[&nov7].len(), 3);
+        assert_eq!(res.results[&nov8].len(), 2);
+        assert_eq!(
+            res.results[&nov7][0].time,
+            Some(Utc.timestamp_millis_opt(1604781723000).unwrap())
+        );
+        assert_eq!(res.results[&nov7][1].format, MatchFormat::Map(Map::Dust2));
     }
 
     #[test]
@@ -1,14 +1,15 @@
 use std::collections::HashMap;
 
-use scraper::selector;
+use chrono::{DateTime, NaiveDate, Utc};
 use scraper::{Html, Selector};
 
+use super::{entry_time, unix_time_attr, MatchFormat};
 use crate::{
-    api::{ElementRef, ElementRefExt},
+    api::{parse_items, ElementRef, ElementRefExt, MatchId},
     Error, NoneErrorExt, Result,
 };
 
-#[derive(Debug, PartialEq)]
+#[derive(Clone, Debug, PartialEq)]
 pub enum UpcomingMatchTeam {
     Name(String),
     Tbd(String),
@@ -30,7 +31,7 @@ impl UpcomingMatchTeam {
     }
 }
 
-#[derive(Debug, PartialEq)]
+#[derive(Clone, Debug, PartialEq)]
 pub enum UpcomingMatchTeams {
     Empty {
         description: String,
@@ -42,16 +43,26 @@ pub enum UpcomingMatchTeams {
     },
 }
 
-#[derive(Debug, PartialEq)]
+#[derive(Clone, Debug, PartialEq)]
 pub struct UpcomingMatch {
-    teams: UpcomingMatchTeams,
-    time: String,  // TODO: normal time type
-    rating: usize, // num of stars
-    meta: String,  // format
-    link: String,
+    pub teams: UpcomingMatchTeams,
+    /// Start time, if known.
+    pub time: Option<DateTime<Utc>>,
+    /// Time as shown on page, like "21:30" or "LIVE".
+    pub time_text: Option<String>,
+    /// Number of stars.
+    pub rating: usize,
+    /// Format of match.
+    pub format: MatchFormat,
+    pub link: String,
 }
 
 impl UpcomingMatch {
+    /// ID of match, parsed from link.
+    pub fn id(&self) -> Result<MatchId> {
+        MatchId::from_link(&self.link)
+    }
+
     // TODO: doc
     pub fn from_element_ref(element: ElementRef) -> Result<Self> {
         let link = element
@@ -61,18 +72,23 @@ impl UpcomingMatch {
             .attr("href")
             .hltv_parse_err("No href to upcoming match")?
             .into();
-        let time = element
-            .select_one(".matchInfo>.matchTime")?
-            .hltv_parse_err("Failed to find match time")?
-            .text2();
+        let time_elem = element.select_one(".matchInfo>.matchTime")?;
+        let time = match (entry_time(element)?, time_elem) {
+            (Some(time), _) => Some(time),
+            (None, Some(elem)) => unix_time_attr(elem, "data-unix")?,
+            (None, None) => None,
+        };
+        let time_text = time_elem.map(|elem| elem.text2());
         let rating = element
             .select(&Selector::parse(".matchInfo>.matchRating>.fa-star")?)
             .filter(|elem| !elem.value().classes().any(|v| v == "faded"))
             .count();
-        let meta = element
-            .select_one(".matchInfo>.matchMeta")?
-            .hltv_parse_err("Failed to get match meta (format)")?
-            .text2();
+        let format = MatchFormat::from_text(
+            &element
+                .select_one(".matchInfo>.matchMeta")?
+                .hltv_parse_err("Failed to get match meta (format)")?
+                .text2(),
+        );
 
         let teams = if let Some(elem) = element.select_one(".matchInfoEmpty>span")? {
             UpcomingMatchTeams::Empty {
@@ -104,20 +120,42 @@ impl UpcomingMatch {
         Ok(Self {
             teams,
             time,
+            time_text,
             rating,
-            meta,
+            format,
             link,
         })
     }
 }
 
+/// Parse date from day headline like "Saturday - 2021-02-13".
+fn parse_day_date(headline: &str) -> Result<NaiveDate> {
+    let date = headline.rsplit(" - ").next().unwrap_or_default().trim();
+    NaiveDate::parse_from_str(date, "%Y-%m-%d")
+        .map_err(|_| Error::HltvParse(format!("Invalid match day '{}'", headline)))
+}
+
+#[derive(Clone, Debug, PartialEq)]
 pub struct UpcomingMatches {
-    pub results: HashMap<String, Vec<UpcomingMatch>>,
+    /// Matches by day.
+    pub results: HashMap<NaiveDate, Vec<UpcomingMatch>>,
 }
 
 impl UpcomingMatches {
     // TODO: doc
     pub fn from_html(document: &Html) -> Result<Self> {
+        enter_span!(
+            "from_html",
+            page = "UpcomingMatches",
+            items_parsed = tracing::field::Empty,
+            parse_failed = tracing::field::Empty,
+        );
+        let res = Self::parse(document);
+        crate::trace::record_parsed(&res, |parsed| parsed.results.values().map(Vec::len).sum());
+        res
+    }
+
+    fn parse(document: &Html) -> Result<Self> {
         document
             .select(&Selector::parse(
                 ".upcomingMatchesContainer>div>div.upcomingMatchesSection",
@@ -128,11 +166,12 @@ impl UpcomingMatches {
                     .hltv_parse_err("Failed to find match day headline")?
                     .text2();
 
-                let results = element
-                    .select(&Selector::parse("div.upcomingMatch")?)
-                    .map(|x| UpcomingMatch::from_element_ref(x))
-                    .collect::<Result<Vec<_>>>()?;
-                Ok((day, results))
+                let results = parse_items(
+                    element.select(&Selector::parse("div.upcomingMatch")?),
+                    UpcomingMatch::from_element_ref,
+                    &day,
+                )?;
+                Ok((parse_day_date(&day)?, results))
             })
             .collect::<Result<HashMap<_, _>>>()
             .map(|results| Self { results })
@@ -143,6 +182,7 @@ impl UpcomingMatches {
 mod tests {
     use super::*;
 
+    use chrono::TimeZone;
     use scraper::{Html, Selector};
 
     fn get_elem<'a>(html: &'a Html, css_selector: &'_ str) -> ElementRef<'a> {
@@ -223,15 +263,46 @@ mod tests {
                     team2: UpcomingMatchTeam::Tbd("ENCE/BIG winner".into()),
                     event: "IEM Beijing-Haidian 2020 Europe".into(),
                 },
-                time: "20:30".into(),
+                time: Some(Utc.timestamp_millis_opt(1605547800000).unwrap()),
+                time_text: Some("20:30".into()),
                 rating: 1,
-                meta: "bo3".into(),
+                format: MatchFormat::BestOf(3),
                 link: "/matches/2345214/nip-vs-ence-big-winner-iem-beijing-haidian-2020-europe"
                     .into(),
             })
         );
     }
 
+    #[test]
+    fn parse_upcoming_match_time_fallback() {
+        let entry = |time: &str| {
+            format!(
+                r#"<div class="upcomingMatch"><a href="/matches/2346506/fate-vs-singularity" class="match a-reset">
+  <div class="matchInfo">{}<div class="matchMeta">bo1</div></div>
+  <div class="matchInfoEmpty"><span>Fate vs Singularity</span></div>
+</a></div>"#,
+                time
+            )
+        };
+
+        // Time from `.matchTime` without entry attribute
+        let html = Html::parse_fragment(&entry(
+            r#"<div class="matchTime" data-time-format="HH:mm" data-unix="1613241000000">21:30</div>"#,
+        ));
+        let upcoming = UpcomingMatch::from_element_ref(get_elem(&html, "div")).unwrap();
+        assert_eq!(
+            upcoming.time,
+            Some(Utc.timestamp_millis_opt(1613241000000).unwrap())
+        );
+        assert_eq!(upcoming.time_text.as_deref(), Some("21:30"));
+
+        // Live match has only text
+        let html = Html::parse_fragment(&entry(r#"<div class="matchTime matchLive">LIVE</div>"#));
+        let upcoming = UpcomingMatch::from_element_ref(get_elem(&html, "div")).unwrap();
+        assert_eq!(upcoming.time, None);
+        assert_eq!(upcoming.time_text.as_deref(), Some("LIVE"));
+    }
+
     #[test]
     fn parse_day_results() {
         let html = Html::parse_fragment(
@@ -469,7 +540,12 @@ betting/analytics/2346506/fate-vs-singularity-esea-premier-season-36-europe" cla
 
         let res = UpcomingMatches::from_html(&html).unwrap();
         assert_eq!(res.results.len(), 3);
-        assert_eq!(res.results["Saturday - 2021-02-13"].len(), 1);
-        assert_eq!(res.results["Sunday - 2021-02-14"].len(), 2);
+        let day = |day| NaiveDate::from_ymd_opt(2021, 2, day).unwrap();
+        assert_eq!(res.results[&day(13)].len(), 1);
+        assert_eq!(res.results[&day(14)].len(), 2);
+        assert_eq!(
+            res.results[&day(13)][0].time,
+            Some(Utc.timestamp_millis_opt(1613241000000).unwrap())
+        );
     }
 }
@@ -1,12 +1,28 @@
 pub use articles::*;
+pub use async_api::*;
+pub use builder::HltvApiBuilder;
+pub use ids::*;
 pub use matches::*;
+pub use page::{HltvPage, PageKind};
+pub use parsed_cache::ParsedCacheConfig;
+
+use std::{sync::Mutex, time::Instant};
 
 use scraper::{ElementRef, Html, Selector};
 
-use crate::{AttoHttpcImpl, Error, HttpsClient, Result, HLTV_URL};
+#[cfg(feature = "attohttpc_client")]
+use crate::AttoHttpcImpl;
+use crate::{CacheTtl, CookieJar, HttpsClient, Request, Response, Result, HLTV_URL, NOT_MODIFIED};
+use parsed_cache::ParsedCache;
 
 mod articles;
+mod async_api;
+mod builder;
+mod cloudflare;
+mod ids;
 mod matches;
+mod page;
+mod parsed_cache;
 
 /// Extension trait for `scrapper::ElementRef`.
 trait ElementRefExt {
@@ -46,7 +62,35 @@ pub enum Month {
 }
 
 impl Month {
-    pub fn to_str(&self) -> &'static str {
+    const ALL: [Month; 12] = [
+        Self::January,
+        Self::February,
+        Self::March,
+        Self::April,
+        Self::May,
+        Self::June,
+        Self::July,
+        Self::August,
+        Self::September,
+        Self::October,
+        Self::November,
+        Self::December,
+    ];
+
+    /// Parse English month name, ignoring case.
+    pub fn from_name(name: &str) -> Option<Self> {
+        Self::ALL
+            .iter()
+            .copied()
+            .find(|month| month.to_str().eq_ignore_ascii_case(name))
+    }
+
+    /// Number of month, starting from 1.
+    pub fn number(self) -> u32 {
+        self as u32 + 1
+    }
+
+    pub fn to_str(self) -> &'static str {
         match self {
             Self::January => "january",
             Self::February => "february",
@@ -64,61 +108,411 @@ impl Month {
     }
 }
 
-// HLTV API
+/// Path to main page.
+const MAIN_PAGE_PATH: &str = "/";
+
+/// Path to upcoming matches page.
+const UPCOMING_MATCHES_PATH: &str = "/matches";
+
+/// Path to news archive page for given month.
+fn archived_news_path(year: u16, month: Month) -> String {
+    format!("/news/archive/{}/{}", year, month.to_str())
+}
+
+/// Path to matches results page with given offset (in pages).
+fn matches_results_path(page_offset: Option<u64>) -> String {
+    format!("/results?offset={}", page_offset.unwrap_or_default() * 100)
+}
+
+/// Parse list items, logging failed item with its section and index.
+fn parse_items<'a, T>(
+    elements: impl Iterator<Item = ElementRef<'a>>,
+    parser: fn(ElementRef<'a>) -> Result<T>,
+    section: &str,
+) -> Result<Vec<T>> {
+    elements
+        .enumerate()
+        .map(|(index, element)| {
+            parser(element).inspect_err(|err| crate::trace::item_failed(section, index, err))
+        })
+        .collect()
+}
+
+/// Parse HTML page with provided parser. Shared between sync and async API,
+/// so both always produce the same results.
+fn parse_page<T>(page: &str, parser: impl FnOnce(&Html) -> Result<T>) -> Result<T> {
+    let document = Html::parse_document(page);
+    parser(&document)
+}
+
+/// Headers and cookies, added to every request to HLTV.
+#[derive(Clone, Debug, Default)]
+struct RequestSettings {
+    headers: Vec<(String, String)>,
+    cookies: CookieJar,
+}
+
+impl RequestSettings {
+    fn header(&mut self, name: String, value: String) {
+        self.headers
+            .retain(|(header, _)| !header.eq_ignore_ascii_case(&name));
+        self.headers.push((name, value));
+    }
+
+    /// Build GET request to provided URL.
+    fn request(&self, url: String) -> Request {
+        let request = self
+            .headers
+            .iter()
+            .fold(Request::get(url), |request, (name, value)| {
+                request.with_header(name.as_str(), value.as_str())
+            });
+        request.with_cookies(self.cookies.clone())
+    }
+}
+
+/// Check whether response has validators, so next request to the same page
+/// may be conditional.
+fn has_validators(response: &Response) -> bool {
+    response.header("ETag").is_some() || response.header("Last-Modified").is_some()
+}
+
+/// Convert result of `HttpsClient::execute` to page, optionally detecting
+/// Cloudflare challenges.
+fn page_body(
+    res: std::result::Result<Response, Box<dyn std::error::Error>>,
+    url: &str,
+    detect_blocks: bool,
+) -> Result<String> {
+    let res = res.and_then(|response| {
+        response
+            .into_body()
+            .map_err(|err| Box::new(err) as Box<dyn std::error::Error>)
+    });
+    if detect_blocks {
+        cloudflare::check_page(res, url)
+    } else {
+        res.map_err(|err| crate::Error::from_client_error(err.as_ref(), url))
+    }
+}
+
+/// HLTV API
+///
+/// Can be shared between threads, see `HltvApi::batch` for concurrent
+/// requests.
+///
+/// If client sends conditional requests (see `ConditionalClient`), last parsed
+/// values of pages with validators are kept (up to
+/// `ParsedCacheConfig::max_entries`), so they can be returned when page is not
+/// modified.
+///
+/// Parsed values can also be cached in memory, see
+/// `HltvApi::with_parsed_cache`. Use `HltvApi::builder` to configure client
+/// wrappers (throttling, retries, cache) in one place.
 pub struct HltvApi {
-    https_client: Box<dyn HttpsClient>,
+    https_client: Box<dyn HttpsClient + Send + Sync>,
     hltv_root_url: String,
+    settings: RequestSettings,
+    last_parsed: ParsedCache,
+    parsed_cache: Option<ParsedCache>,
+    detect_blocks: bool,
 }
 
 impl HltvApi {
+    /// Start building `HltvApi` with wrapped client.
+    pub fn builder() -> HltvApiBuilder {
+        HltvApiBuilder::new()
+    }
+
     /// Build new instance of `HltvApi` with provided HTTPS client and HLTV URL.
-    pub fn new<T: HttpsClient + 'static, U: Into<String>>(client: T, hltv_root_url: U) -> Self {
+    pub fn new<T, U>(client: T, hltv_root_url: U) -> Self
+    where
+        T: HttpsClient + Send + Sync + 'static,
+        U: Into<String>,
+    {
         Self {
             https_client: Box::new(client),
             hltv_root_url: hltv_root_url.into(),
+            settings: RequestSettings::default(),
+            last_parsed: ParsedCache::new(ParsedCacheConfig::default()),
+            parsed_cache: None,
+            detect_blocks: true,
         }
     }
 
     /// Build new instance of `HltvApi` with provided HTTPS client and default HLTV URL.
-    pub fn with_default_path<T: HttpsClient + 'static>(client: T) -> Self {
-        Self {
-            https_client: Box::new(client),
-            hltv_root_url: HLTV_URL.into(),
+    pub fn with_default_path<T: HttpsClient + Send + Sync + 'static>(client: T) -> Self {
+        Self::new(client, HLTV_URL)
+    }
+
+    /// Add header to every request, replacing previous value with the same
+    /// name (including `User-Agent`, set by client).
+    pub fn with_header<N: Into<String>, V: Into<String>>(mut self, name: N, value: V) -> Self {
+        self.settings.header(name.into(), value.into());
+        self
+    }
+
+    /// Add cookie to every request.
+    pub fn with_cookie<N: Into<String>, V: Into<String>>(mut self, name: N, value: V) -> Self {
+        self.settings.cookies.insert(name, value);
+        self
+    }
+
+    /// Cache parsed latest news, matches results and upcoming matches in
+    /// memory with provided TTLs. Concurrent calls of the same method with
+    /// the same arguments share single request.
+    pub fn with_parsed_cache(mut self, config: ParsedCacheConfig) -> Self {
+        self.last_parsed = ParsedCache::new(config.clone());
+        self.parsed_cache = Some(ParsedCache::new(config));
+        self
+    }
+
+    /// Remove all values from in-memory cache (see
+    /// `HltvApi::with_parsed_cache`).
+    pub fn clear_parsed_cache(&self) {
+        if let Some(cache) = &self.parsed_cache {
+            cache.clear();
         }
     }
 
+    fn request(&self, path: &str) -> Request {
+        self.settings
+            .request(format!("{}{}", self.hltv_root_url, path))
+    }
+
+    fn execute(
+        &self,
+        request: &Request,
+    ) -> std::result::Result<Response, Box<dyn std::error::Error>> {
+        enter_span!(
+            "http_request",
+            url = %request.url,
+            status = tracing::field::Empty,
+            bytes = tracing::field::Empty,
+            latency_ms = tracing::field::Empty,
+        );
+        let start = Instant::now();
+        let res = self.https_client.execute(request);
+        crate::trace::record_response(&res, start);
+        res
+    }
+
+    /// Get page by path, detecting Cloudflare challenges. Request is never
+    /// conditional.
     fn get_page(&self, path: &str) -> Result<String> {
-        self.https_client
-            .get(&format!("{}{}", self.hltv_root_url, path))
-            .map_err(|err| Error::HttpsClient(err))
+        let request = self.request(path).unconditional();
+        page_body(self.execute(&request), &request.url, self.detect_blocks)
+    }
+
+    /// Get and parse page, returning `None` if it is not modified since
+    /// previous request.
+    fn get_parsed_if_modified<T: Clone + Send + 'static>(
+        &self,
+        path: &str,
+        parser: fn(&Html) -> Result<T>,
+    ) -> Result<Option<T>> {
+        let request = self.request(path);
+        let res = self.execute(&request);
+        if res
+            .as_ref()
+            .is_ok_and(|response| response.status == NOT_MODIFIED)
+        {
+            return Ok(None);
+        }
+
+        let cacheable = res.as_ref().is_ok_and(has_validators);
+        let value = parse_page(&page_body(res, &request.url, self.detect_blocks)?, parser)?;
+        if cacheable && self.https_client.is_conditional() {
+            self.last_parsed.insert(path, value.clone());
+        } else {
+            self.last_parsed.remove(path);
+        }
+        Ok(Some(value))
+    }
+
+    /// Get and parse page. If page is not modified, previously parsed value
+    /// is returned.
+    fn get_parsed<T: Clone + Send + 'static>(
+        &self,
+        path: &str,
+        parser: fn(&Html) -> Result<T>,
+    ) -> Result<T> {
+        if let Some(value) = self.get_parsed_if_modified(path, parser)? {
+            return Ok(value);
+        }
+        match self.last_parsed.get(path) {
+            Some(value) => Ok(value),
+            // Page was revalidated by other instance, so fetch it again
+            None => parse_page(&self.get_page(path)?, parser),
+        }
+    }
+
+    /// Get parsed page from in-memory cache, if it is enabled, or fetch it.
+    fn get_parsed_cached<T: Clone + Send + 'static>(
+        &self,
+        path: &str,
+        ttl: fn(&ParsedCacheConfig) -> CacheTtl,
+        parser: fn(&Html) -> Result<T>,
+    ) -> Result<T> {
+        match &self.parsed_cache {
+            Some(cache) => {
+                cache.get_or_fetch(path, ttl(&cache.config), || self.get_parsed(path, parser))
+            }
+            None => self.get_parsed(path, parser),
+        }
     }
 
     /// Get news briefs from main page (ie latest news).
     pub fn latest_news_briefs(&self) -> Result<MainPageArticleBriefs> {
-        let document = Html::parse_document(&self.get_page("/")?);
-        MainPageArticleBriefs::from_html(&document)
+        enter_span!("hltv_api", method = "latest_news_briefs");
+        self.get_parsed_cached(
+            MAIN_PAGE_PATH,
+            |config| config.latest_news_briefs,
+            MainPageArticleBriefs::from_html,
+        )
     }
 
     /// Get news briefs from archive.
     pub fn archived_news_briefs(&self, year: u16, month: Month) -> Result<Vec<ArticleBrief>> {
-        let path = format!("/news/archive/{}/{}", year, month.to_str());
-        let document = Html::parse_document(&self.get_page(&path)?);
-        archived_article_briefs_from_html(&document)
+        enter_span!("hltv_api", method = "archived_news_briefs");
+        self.get_parsed(
+            &archived_news_path(year, month),
+            archived_article_briefs_from_html,
+        )
     }
 
     /// Get matches results.
     pub fn matches_results(&self, page_offset: Option<u64>) -> Result<MatchesResults> {
-        let path = format!("/results?offset={}", page_offset.unwrap_or_default() * 100);
-        let document = Html::parse_document(&self.get_page(&path)?);
-        MatchesResults::from_html(&document)
+        enter_span!("hltv_api", method = "matches_results");
+        self.get_parsed_cached(
+            &matches_results_path(page_offset),
+            |config| config.matches_results,
+            MatchesResults::from_html,
+        )
+    }
+
+    /// Get matches results or `None`, if they are not modified since previous
+    /// request (requires client, which sends conditional requests, like
+    /// `ConditionalClient`).
+    pub fn matches_results_if_modified(
+        &self,
+        page_offset: Option<u64>,
+    ) -> Result<Option<MatchesResults>> {
+        enter_span!("hltv_api", method = "matches_results_if_modified");
+        self.get_parsed_if_modified(
+            &matches_results_path(page_offset),
+            MatchesResults::from_html,
+        )
     }
 
     /// Get upcoming matches.
     pub fn upcoming_matches(&self) -> Result<UpcomingMatches> {
-        let path = format!("/matches");
-        let document = Html::parse_document(&self.get_page(&path)?);
-        //println!("{}", document.root_element().html());
-        UpcomingMatches::from_html(&document)
+        enter_span!("hltv_api", method = "upcoming_matches");
+        self.get_parsed_cached(
+            UPCOMING_MATCHES_PATH,
+            |config| config.upcoming_matches,
+            UpcomingMatches::from_html,
+        )
+    }
+
+    /// Get upcoming matches or `None`, if they are not modified since previous
+    /// request (requires client, which sends conditional requests, like
+    /// `ConditionalClient`).
+    pub fn upcoming_matches_if_modified(&self) -> Result<Option<UpcomingMatches>> {
+        enter_span!("hltv_api", method = "upcoming_matches_if_modified");
+        self.get_parsed_if_modified(UPCOMING_MATCHES_PATH, UpcomingMatches::from_html)
+    }
+
+    /// Get raw HTML of page by path (like `/matches`), for example to store
+    /// it alongside parsed result (see `HltvPage`). Cloudflare challenges are
+    /// detected, if enabled, but in-memory cache is not used.
+    pub fn raw_page(&self, path: &str) -> Result<String> {
+        enter_span!("hltv_api", method = "raw_page");
+        self.get_page(path)
+    }
+
+    /// Get raw HTML of entity page, like match or news article.
+    pub fn entity_page<I: EntityId>(&self, id: &I) -> Result<String> {
+        enter_span!("hltv_api", method = "entity_page");
+        self.get_page(&id.path())
+    }
+
+    /// Get raw HTML of main page.
+    pub fn latest_news_page(&self) -> Result<String> {
+        self.raw_page(MAIN_PAGE_PATH)
+    }
+
+    /// Get raw HTML of news archive page.
+    pub fn archived_news_page(&self, year: u16, month: Month) -> Result<String> {
+        self.raw_page(&archived_news_path(year, month))
+    }
+
+    /// Get raw HTML of matches results page.
+    pub fn matches_results_page(&self, page_offset: Option<u64>) -> Result<String> {
+        self.raw_page(&matches_results_path(page_offset))
+    }
+
+    /// Get raw HTML of upcoming matches page.
+    pub fn upcoming_matches_page(&self) -> Result<String> {
+        self.raw_page(UPCOMING_MATCHES_PATH)
+    }
+
+    /// Call `fetch` for every argument, running up to `concurrency` requests
+    /// in parallel threads. Results are returned in order of arguments.
+    pub fn batch<A, T, F>(&self, args: Vec<A>, concurrency: usize, fetch: F) -> Vec<Result<T>>
+    where
+        A: Send,
+        T: Send,
+        F: Fn(&Self, A) -> Result<T> + Sync,
+    {
+        let count = args.len();
+        let jobs = Mutex::new(args.into_iter().enumerate());
+        let results = Mutex::new((0..count).map(|_| None).collect::<Vec<_>>());
+
+        std::thread::scope(|scope| {
+            for _ in 0..concurrency.clamp(1, count.max(1)) {
+                scope.spawn(|| loop {
+                    let job = jobs.lock().unwrap_or_else(|err| err.into_inner()).next();
+                    let (idx, arg) = match job {
+                        Some(job) => job,
+                        None => break,
+                    };
+                    let res = fetch(self, arg);
+                    results.lock().unwrap_or_else(|err| err.into_inner())[idx] = Some(res);
+                });
+            }
+        });
+
+        results
+            .into_inner()
+            .unwrap_or_else(|err| err.into_inner())
+            .into_iter()
+            .map(|res| res.expect("Every batch job is finished"))
+            .collect()
+    }
+
+    /// Get matches results for several page offsets concurrently (see
+    /// `HltvApi::batch`).
+    pub fn matches_results_batch(
+        &self,
+        page_offsets: Vec<Option<u64>>,
+        concurrency: usize,
+    ) -> Vec<Result<MatchesResults>> {
+        self.batch(page_offsets, concurrency, |api, page_offset| {
+            api.matches_results(page_offset)
+        })
+    }
+
+    /// Get news briefs from archive for several months concurrently (see
+    /// `HltvApi::batch`).
+    pub fn archived_news_briefs_batch(
+        &self,
+        months: Vec<(u16, Month)>,
+        concurrency: usize,
+    ) -> Vec<Result<Vec<ArticleBrief>>> {
+        self.batch(months, concurrency, |api, (year, month)| {
+            api.archived_news_briefs(year, month)
+        })
     }
 }
 
@@ -126,31 +520,49 @@ impl HltvApi {
 #[cfg(feature = "attohttpc_client")]
 impl Default for HltvApi {
     fn default() -> Self {
-        Self {
-            https_client: Box::new(AttoHttpcImpl {}),
-            hltv_root_url: HLTV_URL.into(),
-        }
+        Self::with_default_path(AttoHttpcImpl::new())
     }
 }
 
-// TODO: throttle requests to HLTV, otherwise get banned by Cloudflare
 #[cfg(all(test, feature = "attohttpc_client"))]
 mod tests {
+    use std::sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc,
+    };
+
     use super::*;
+    use crate::{ConditionalClient, RecordingClient, ReplayClient};
+
+    /// Directory with cassettes. Checked in cassettes are hand-written
+    /// fixtures in HLTV markup without response headers, not recorded pages.
+    const CASSETTES_DIR: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/tests/cassettes");
+
+    /// API, which serves pages from cassettes. Set `HLTV_RECORD` environment
+    /// variable to re-record them from live HLTV.
+    fn api() -> HltvApi {
+        if std::env::var_os("HLTV_RECORD").is_some() {
+            HltvApi::with_default_path(
+                RecordingClient::new(AttoHttpcImpl::new(), CASSETTES_DIR).unwrap(),
+            )
+        } else {
+            HltvApi::with_default_path(ReplayClient::new(CASSETTES_DIR))
+        }
+    }
 
     #[test]
     fn get_hltv_root() {
-        assert!(HltvApi::default().get_page("/").is_ok());
+        assert!(api().get_page("/").is_ok());
     }
 
     #[test]
     fn get_hltv_404() {
-        assert!(HltvApi::default().get_page("/unknown_resource").is_err());
+        assert!(api().get_page("/unknown_resource").is_err());
     }
 
     #[test]
     fn latest_news_briefs() {
-        assert!(HltvApi::default().latest_news_briefs().is_ok());
+        assert!(api().latest_news_briefs().is_ok());
     }
 
     #[test]
@@ -170,27 +582,206 @@ mod tests {
             Month::December,
         ];
 
+        let mut paths = std::collections::HashSet::new();
         for month in months {
-            assert!(
-                HltvApi::default().archived_news_briefs(2019, month).is_ok(),
-                format!("News for {:?} 2019 is not OK", month)
-            );
+            let briefs = api().archived_news_briefs(2019, month).unwrap();
+            assert!(!briefs.is_empty(), "No news for {:?} 2019", month);
+            for brief in briefs {
+                let prefix = format!("2019-{:02}-", month.number());
+                assert!(brief.when.starts_with(&prefix), "{:?}", brief);
+                assert!(paths.insert(brief.path));
+            }
         }
     }
 
     #[test]
     fn matches_results() {
-        assert!(HltvApi::default().matches_results(None).is_ok());
+        assert!(api().matches_results(None).is_ok());
     }
 
     #[test]
     fn matches_results_page_offset() {
-        assert!(HltvApi::default().matches_results(Some(5)).is_ok());
+        let first = api().matches_results(None).unwrap();
+        let res = api().matches_results(Some(5)).unwrap();
+        // Next pages contain older results
+        let first_oldest = first.results.keys().min().unwrap();
+        assert!(!res.results.is_empty());
+        assert!(res.results.keys().all(|day| day <= first_oldest));
+        assert!(res.results.values().flatten().all(|result| !first
+            .results
+            .values()
+            .flatten()
+            .any(|other| other == result)));
     }
 
     #[test]
     fn upcoming_matches() {
-        HltvApi::default().upcoming_matches().unwrap();
-        assert!(HltvApi::default().upcoming_matches().is_ok());
+        api().upcoming_matches().unwrap();
+        assert!(api().upcoming_matches().is_ok());
+    }
+
+    /// Client, which serves cassettes with constant ETag.
+    struct EtagClient(ReplayClient);
+
+    impl HttpsClient for EtagClient {
+        fn get(&self, url: &str) -> std::result::Result<String, Box<dyn std::error::Error>> {
+            crate::get_via_execute(self, url)
+        }
+
+        fn execute(
+            &self,
+            request: &Request,
+        ) -> std::result::Result<Response, Box<dyn std::error::Error>> {
+            if request.header("If-None-Match") == Some("\"v1\"") {
+                return Ok(Response {
+                    status: NOT_MODIFIED,
+                    ..Response::ok(request.url.as_str(), "")
+                });
+            }
+            let mut response = self.0.execute(request)?;
+            response.headers.push(("ETag".into(), "\"v1\"".into()));
+            Ok(response)
+        }
+    }
+
+    #[test]
+    fn api_is_send_sync() {
+        fn assert_send_sync<T: Send + Sync>() {}
+        assert_send_sync::<HltvApi>();
+    }
+
+    #[test]
+    fn batch() {
+        let api = api();
+        let offsets = vec![Some(5), None, Some(5), None];
+        let results = api.matches_results_batch(offsets.clone(), 3);
+        assert_eq!(results.len(), offsets.len());
+        for (offset, res) in offsets.into_iter().zip(results) {
+            assert_eq!(res, api.matches_results(offset));
+        }
+        assert_ne!(
+            api.matches_results(Some(5)).unwrap(),
+            api.matches_results(None).unwrap()
+        );
+
+        let months = vec![(2019, Month::May), (2077, Month::May), (2019, Month::June)];
+        let results = api.archived_news_briefs_batch(months, 2);
+        assert!(results[0].is_ok());
+        assert!(results[1].is_err());
+        assert_eq!(results[2], api.archived_news_briefs(2019, Month::June));
+        assert_ne!(results[0], results[2]);
+
+        assert!(api.matches_results_batch(Vec::new(), 2).is_empty());
+    }
+
+    #[test]
+    fn raw_pages() {
+        let api = api();
+        let html = api.matches_results_page(None).unwrap();
+        assert_eq!(
+            HltvPage::parse(&html).unwrap(),
+            HltvPage::MatchesResults(api.matches_results(None).unwrap())
+        );
+        assert_eq!(
+            PageKind::detect(&api.upcoming_matches_page().unwrap()),
+            Some(PageKind::UpcomingMatches)
+        );
+        assert!(api.raw_page("/unknown_resource").is_err());
+    }
+
+    #[test]
+    fn entity_ids() {
+        let api = api();
+        let results = api.matches_results(None).unwrap();
+        for result in results.results.values().flatten() {
+            assert_eq!(result.id().unwrap().path(), result.link);
+        }
+        let matches = api.upcoming_matches().unwrap();
+        for upcoming in matches.results.values().flatten() {
+            assert!(upcoming.id().is_ok());
+        }
+        let briefs = api.latest_news_briefs().unwrap();
+        assert!(briefs.today.iter().all(|brief| brief.id().is_ok()));
+        // Not recorded
+        assert!(api.entity_page(&MatchId::new(1)).is_err());
+    }
+
+    #[test]
+    fn conditional_requests() {
+        let client = ConditionalClient::new(EtagClient(ReplayClient::new(CASSETTES_DIR)));
+        let api = HltvApi::with_default_path(client);
+
+        let matches = api.upcoming_matches_if_modified().unwrap().unwrap();
+        assert!(api
+            .last_parsed
+            .get::<UpcomingMatches>(UPCOMING_MATCHES_PATH)
+            .is_some());
+        assert_eq!(api.upcoming_matches_if_modified().unwrap(), None);
+        // Not modified page is served from last parsed value
+        assert_eq!(api.upcoming_matches().unwrap(), matches);
+
+        let results = api.matches_results(None).unwrap();
+        assert_eq!(api.matches_results(None).unwrap(), results);
+        assert_eq!(api.matches_results_if_modified(None).unwrap(), None);
+
+        // Without `ConditionalClient` parsed values are not kept
+        let api = HltvApi::with_default_path(EtagClient(ReplayClient::new(CASSETTES_DIR)));
+        api.upcoming_matches().unwrap();
+        assert_eq!(
+            api.last_parsed
+                .get::<UpcomingMatches>(UPCOMING_MATCHES_PATH),
+            None
+        );
+    }
+
+    /// Client, which counts requests.
+    struct CountingClient {
+        client: ReplayClient,
+        requests: Arc<AtomicUsize>,
+    }
+
+    impl HttpsClient for CountingClient {
+        fn get(&self, url: &str) -> std::result::Result<String, Box<dyn std::error::Error>> {
+            crate::get_via_execute(self, url)
+        }
+
+        fn execute(
+            &self,
+            request: &Request,
+        ) -> std::result::Result<Response, Box<dyn std::error::Error>> {
+            self.requests.fetch_add(1, Ordering::SeqCst);
+            self.client.execute(request)
+        }
+    }
+
+    #[test]
+    fn parsed_cache() {
+        let counter = Arc::new(AtomicUsize::new(0));
+        let client = CountingClient {
+            client: ReplayClient::new(CASSETTES_DIR),
+            requests: counter.clone(),
+        };
+        let api = HltvApi::with_default_path(client).with_parsed_cache(ParsedCacheConfig {
+            matches_results: CacheTtl::NoCache,
+            ..ParsedCacheConfig::default()
+        });
+        let requests = || counter.load(Ordering::SeqCst);
+
+        let matches = api.upcoming_matches().unwrap();
+        assert_eq!(api.upcoming_matches().unwrap(), matches);
+        assert_eq!(requests(), 1);
+
+        api.matches_results(None).unwrap();
+        api.matches_results(None).unwrap();
+        assert_eq!(requests(), 3);
+
+        api.clear_parsed_cache();
+        api.upcoming_matches().unwrap();
+        assert_eq!(requests(), 4);
+
+        // Archive is not cached
+        api.archived_news_briefs(2019, Month::May).unwrap();
+        api.archived_news_briefs(2019, Month::May).unwrap();
+        assert_eq!(requests(), 6);
     }
 }
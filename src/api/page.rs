@@ -0,0 +1,191 @@
+use std::{fs, io::Read, path::Path};
+
+use scraper::{Html, Selector};
+
+use super::{
+    archived_article_briefs_from_html, ArticleBrief, ElementRefExt, MainPageArticleBriefs,
+    MatchesResults, UpcomingMatches,
+};
+use crate::{detect_block, Error, NoneErrorExt, Result};
+
+/// Kind of supported HLTV page.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum PageKind {
+    /// Main page with latest news.
+    LatestNews,
+    /// News archive for month.
+    ArchivedNews,
+    MatchesResults,
+    UpcomingMatches,
+}
+
+impl PageKind {
+    /// Detect kind of page by its content.
+    pub fn detect(html: &str) -> Option<Self> {
+        Self::detect_document(&Html::parse_document(html))
+    }
+
+    fn detect_document(document: &Html) -> Option<Self> {
+        let has = |selector: &str| {
+            Selector::parse(selector)
+                .ok()
+                .and_then(|selector| document.select(&selector).next())
+                .is_some()
+        };
+        if has(".upcomingMatchesContainer") {
+            return Some(Self::UpcomingMatches);
+        }
+        if has("div.results-holder") {
+            return Some(Self::MatchesResults);
+        }
+        // Archive has single "News from <Month>, <year>" header, main page has
+        // "Today's news", "Yesterday's news" and so on
+        let selector = Selector::parse("h2.newsheader").ok()?;
+        let header = document.select(&selector).next()?.text2();
+        if header.trim_start().starts_with("News from") {
+            Some(Self::ArchivedNews)
+        } else {
+            Some(Self::LatestNews)
+        }
+    }
+
+    /// Parse page of this kind.
+    pub fn parse(self, html: &str) -> Result<HltvPage> {
+        check_block(html)?;
+        self.parse_document(&Html::parse_document(html))
+    }
+
+    fn parse_document(self, document: &Html) -> Result<HltvPage> {
+        Ok(match self {
+            Self::LatestNews => HltvPage::LatestNews(MainPageArticleBriefs::from_html(document)?),
+            Self::ArchivedNews => {
+                HltvPage::ArchivedNews(archived_article_briefs_from_html(document)?)
+            }
+            Self::MatchesResults => HltvPage::MatchesResults(MatchesResults::from_html(document)?),
+            Self::UpcomingMatches => {
+                HltvPage::UpcomingMatches(UpcomingMatches::from_html(document)?)
+            }
+        })
+    }
+}
+
+/// Parsed HLTV page of any supported kind.
+///
+/// Allows to parse pages, saved to disk, without `HltvApi`. Raw pages can be
+/// fetched with `HltvApi::raw_page` and similar methods.
+#[derive(Clone, Debug, PartialEq)]
+pub enum HltvPage {
+    LatestNews(MainPageArticleBriefs),
+    ArchivedNews(Vec<ArticleBrief>),
+    MatchesResults(MatchesResults),
+    UpcomingMatches(UpcomingMatches),
+}
+
+impl HltvPage {
+    /// Parse page, detecting its kind.
+    pub fn parse(html: &str) -> Result<Self> {
+        check_block(html)?;
+        let document = Html::parse_document(html);
+        PageKind::detect_document(&document)
+            .hltv_parse_err("Unknown page kind")?
+            .parse_document(&document)
+    }
+
+    /// Read page from reader and parse it, detecting its kind.
+    pub fn parse_reader<R: Read>(mut reader: R) -> Result<Self> {
+        let mut html = String::new();
+        reader.read_to_string(&mut html)?;
+        Self::parse(&html)
+    }
+
+    /// Read page from file and parse it, detecting its kind.
+    pub fn parse_file<P: AsRef<Path>>(path: P) -> Result<Self> {
+        Self::parse(&fs::read_to_string(path)?)
+    }
+
+    pub fn kind(&self) -> PageKind {
+        match self {
+            Self::LatestNews(_) => PageKind::LatestNews,
+            Self::ArchivedNews(_) => PageKind::ArchivedNews,
+            Self::MatchesResults(_) => PageKind::MatchesResults,
+            Self::UpcomingMatches(_) => PageKind::UpcomingMatches,
+        }
+    }
+}
+
+/// Saved Cloudflare page is reported as block, not as unknown page.
+fn check_block(html: &str) -> Result<()> {
+    match detect_block(html) {
+        Some(kind) => Err(Error::Blocked {
+            kind,
+            retry_after: None,
+        }),
+        None => Ok(()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CASSETTES_DIR: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/tests/cassettes");
+
+    /// Read page body from cassette.
+    fn page(name: &str) -> String {
+        let content = fs::read_to_string(Path::new(CASSETTES_DIR).join(name)).unwrap();
+        content.split_once("\n\n").unwrap().1.into()
+    }
+
+    #[test]
+    fn detect() {
+        let pages = [
+            ("www_hltv_org_-ef161cd4.cassette", PageKind::LatestNews),
+            (
+                "www_hltv_org_news_archive_2019_may-cb4ba811.cassette",
+                PageKind::ArchivedNews,
+            ),
+            (
+                "www_hltv_org_results_offset_0-207daf91.cassette",
+                PageKind::MatchesResults,
+            ),
+            (
+                "www_hltv_org_matches-45ec7811.cassette",
+                PageKind::UpcomingMatches,
+            ),
+        ];
+        for (name, kind) in pages.iter() {
+            let html = page(name);
+            assert_eq!(PageKind::detect(&html), Some(*kind), "{}", name);
+            assert_eq!(HltvPage::parse(&html).unwrap().kind(), *kind, "{}", name);
+        }
+        assert_eq!(PageKind::detect("<html></html>"), None);
+    }
+
+    #[test]
+    fn parse_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("results.html");
+        let html = page("www_hltv_org_results_offset_0-207daf91.cassette");
+        fs::write(&path, &html).unwrap();
+
+        let parsed = HltvPage::parse_file(&path).unwrap();
+        assert_eq!(parsed, PageKind::MatchesResults.parse(&html).unwrap());
+        assert_eq!(HltvPage::parse_reader(html.as_bytes()).unwrap(), parsed);
+        assert!(matches!(
+            HltvPage::parse_file(dir.path().join("missing.html")),
+            Err(Error::Io(_))
+        ));
+    }
+
+    #[test]
+    fn parse_errors() {
+        assert!(matches!(
+            HltvPage::parse("<html></html>"),
+            Err(Error::HltvParse(_))
+        ));
+        assert!(matches!(
+            HltvPage::parse("<title>Just a moment...</title>"),
+            Err(Error::Blocked { .. })
+        ));
+    }
+}
@@ -0,0 +1,257 @@
+use std::{
+    any::Any,
+    collections::HashMap,
+    sync::{Condvar, Mutex, MutexGuard},
+    time::{Duration, Instant},
+};
+
+use crate::{CacheTtl, Result};
+
+/// TTLs of parsed values, cached by `HltvApi` (see
+/// `HltvApi::with_parsed_cache`). `max_entries` also limits last parsed
+/// values, kept for conditional requests.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ParsedCacheConfig {
+    /// TTL of `HltvApi::latest_news_briefs`.
+    pub latest_news_briefs: CacheTtl,
+    /// TTL of `HltvApi::matches_results` (for every page offset).
+    pub matches_results: CacheTtl,
+    /// TTL of `HltvApi::upcoming_matches`.
+    pub upcoming_matches: CacheTtl,
+    /// Maximal number of cached values, oldest values are evicted first.
+    pub max_entries: usize,
+}
+
+impl Default for ParsedCacheConfig {
+    fn default() -> Self {
+        Self {
+            latest_news_briefs: CacheTtl::Expires(Duration::from_secs(60)),
+            matches_results: CacheTtl::Expires(Duration::from_secs(60)),
+            upcoming_matches: CacheTtl::Expires(Duration::from_secs(60)),
+            max_entries: 128,
+        }
+    }
+}
+
+enum Entry {
+    /// Value is being fetched by other caller.
+    Pending,
+    Ready {
+        value: Box<dyn Any + Send>,
+        stored: Instant,
+    },
+}
+
+/// In-memory cache of parsed values, keyed by page path.
+///
+/// Concurrent requests for the same key are coalesced: only first caller
+/// fetches value, others wait for it. If fetch fails, waiting callers fetch
+/// value themselves.
+pub(crate) struct ParsedCache {
+    pub(crate) config: ParsedCacheConfig,
+    entries: Mutex<HashMap<String, Entry>>,
+    fetched: Condvar,
+}
+
+/// Removes pending entry, if fetch failed or panicked.
+struct PendingGuard<'a> {
+    cache: &'a ParsedCache,
+    key: &'a str,
+}
+
+impl Drop for PendingGuard<'_> {
+    fn drop(&mut self) {
+        let mut entries = self.cache.lock();
+        if let Some(Entry::Pending) = entries.get(self.key) {
+            entries.remove(self.key);
+        }
+        self.cache.fetched.notify_all();
+    }
+}
+
+impl ParsedCache {
+    pub(crate) fn new(config: ParsedCacheConfig) -> Self {
+        Self {
+            config,
+            entries: Mutex::new(HashMap::new()),
+            fetched: Condvar::new(),
+        }
+    }
+
+    fn lock(&self) -> MutexGuard<'_, HashMap<String, Entry>> {
+        self.entries.lock().unwrap_or_else(|err| err.into_inner())
+    }
+
+    /// Get cached value or fetch it. Value, fetched while caller was
+    /// waiting, is returned even if `ttl` is `NoCache`.
+    pub(crate) fn get_or_fetch<T, F>(&self, key: &str, ttl: CacheTtl, fetch: F) -> Result<T>
+    where
+        T: Clone + Send + 'static,
+        F: FnOnce() -> Result<T>,
+    {
+        let mut entries = self.lock();
+        let mut waited = false;
+        loop {
+            match entries.get(key) {
+                Some(Entry::Pending) => {
+                    entries = self
+                        .fetched
+                        .wait(entries)
+                        .unwrap_or_else(|err| err.into_inner());
+                    waited = true;
+                    continue;
+                }
+                Some(Entry::Ready { value, stored }) if waited || is_fresh(*stored, ttl) => {
+                    if let Some(value) = value.downcast_ref::<T>() {
+                        return Ok(value.clone());
+                    }
+                }
+                _ => {}
+            }
+            break;
+        }
+        entries.insert(key.into(), Entry::Pending);
+        drop(entries);
+
+        let guard = PendingGuard { cache: self, key };
+        let value = fetch()?;
+        self.insert(key, value.clone());
+        drop(guard);
+        Ok(value)
+    }
+
+    /// Get cached value regardless of its age.
+    pub(crate) fn get<T: Clone + 'static>(&self, key: &str) -> Option<T> {
+        match self.lock().get(key) {
+            Some(Entry::Ready { value, .. }) => value.downcast_ref::<T>().cloned(),
+            _ => None,
+        }
+    }
+
+    /// Store value, evicting oldest values if cache is full.
+    pub(crate) fn insert<T: Send + 'static>(&self, key: &str, value: T) {
+        let mut entries = self.lock();
+        entries.insert(
+            key.into(),
+            Entry::Ready {
+                value: Box::new(value),
+                stored: Instant::now(),
+            },
+        );
+        while entries.len() > self.config.max_entries {
+            let oldest = entries
+                .iter()
+                .filter_map(|(key, entry)| match entry {
+                    Entry::Ready { stored, .. } => Some((key, *stored)),
+                    Entry::Pending => None,
+                })
+                .min_by_key(|(_, stored)| *stored)
+                .map(|(key, _)| key.clone());
+            match oldest {
+                Some(oldest) => entries.remove(&oldest),
+                // Only pending entries are left
+                None => break,
+            };
+        }
+    }
+
+    /// Remove value, unless it is being fetched.
+    pub(crate) fn remove(&self, key: &str) {
+        let mut entries = self.lock();
+        if let Some(Entry::Ready { .. }) = entries.get(key) {
+            entries.remove(key);
+        }
+    }
+
+    pub(crate) fn clear(&self) {
+        self.lock()
+            .retain(|_, entry| matches!(entry, Entry::Pending));
+    }
+}
+
+fn is_fresh(stored: Instant, ttl: CacheTtl) -> bool {
+    match ttl {
+        CacheTtl::NoCache => false,
+        CacheTtl::Expires(ttl) => stored.elapsed() <= ttl,
+        CacheTtl::Forever => true,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::{AtomicU32, Ordering};
+
+    use super::*;
+    use crate::Error;
+
+    #[test]
+    fn ttl() {
+        let cache = ParsedCache::new(ParsedCacheConfig::default());
+        let calls = AtomicU32::new(0);
+        let fetch = || Ok(calls.fetch_add(1, Ordering::SeqCst));
+        let ttl = CacheTtl::Expires(Duration::from_millis(50));
+
+        assert_eq!(cache.get_or_fetch("/", ttl, fetch), Ok(0));
+        assert_eq!(cache.get_or_fetch("/", ttl, fetch), Ok(0));
+        assert_eq!(cache.get_or_fetch("/other", ttl, fetch), Ok(1));
+        assert_eq!(cache.get_or_fetch("/", CacheTtl::NoCache, fetch), Ok(2));
+
+        std::thread::sleep(Duration::from_millis(60));
+        assert_eq!(cache.get_or_fetch("/", ttl, fetch), Ok(3));
+
+        cache.clear();
+        assert_eq!(cache.get_or_fetch("/", CacheTtl::Forever, fetch), Ok(4));
+    }
+
+    #[test]
+    fn eviction() {
+        let cache = ParsedCache::new(ParsedCacheConfig {
+            max_entries: 2,
+            ..ParsedCacheConfig::default()
+        });
+        cache.insert("/a", 1);
+        std::thread::sleep(Duration::from_millis(1));
+        cache.insert("/b", 2);
+        std::thread::sleep(Duration::from_millis(1));
+        cache.insert("/c", 3);
+        assert_eq!(cache.get::<i32>("/a"), None);
+        assert_eq!(cache.get::<i32>("/b"), Some(2));
+        assert_eq!(cache.get::<i32>("/c"), Some(3));
+
+        cache.remove("/b");
+        assert_eq!(cache.get::<i32>("/b"), None);
+    }
+
+    #[test]
+    fn errors_are_not_cached() {
+        let cache = ParsedCache::new(ParsedCacheConfig::default());
+        let res: Result<u32> = cache.get_or_fetch("/", CacheTtl::Forever, || {
+            Err(Error::HltvParse("test".into()))
+        });
+        assert!(res.is_err());
+        assert_eq!(cache.get_or_fetch("/", CacheTtl::Forever, || Ok(1)), Ok(1));
+    }
+
+    #[test]
+    fn coalesce() {
+        let cache = ParsedCache::new(ParsedCacheConfig::default());
+        let calls = AtomicU32::new(0);
+
+        std::thread::scope(|scope| {
+            let handles = (0..4)
+                .map(|_| {
+                    scope.spawn(|| {
+                        cache.get_or_fetch("/", CacheTtl::NoCache, || {
+                            std::thread::sleep(Duration::from_millis(50));
+                            Ok(calls.fetch_add(1, Ordering::SeqCst))
+                        })
+                    })
+                })
+                .collect::<Vec<_>>();
+            for handle in handles {
+                assert_eq!(handle.join().unwrap(), Ok(0));
+            }
+        });
+        assert_eq!(calls.load(Ordering::SeqCst), 1);
+    }
+}
@@ -0,0 +1,367 @@
+use std::{
+    collections::VecDeque,
+    error::Error,
+    sync::{Mutex, MutexGuard},
+    time::{Duration, Instant},
+};
+
+use super::{detect_block, get_via_execute, is_transient_status, HttpsClient, Request, Response};
+
+/// Limit of requests per period.
+#[derive(Clone, Debug, PartialEq)]
+pub struct RequestBudget {
+    pub max_requests: u32,
+    pub period: Duration,
+}
+
+impl RequestBudget {
+    pub fn per_hour(max_requests: u32) -> Self {
+        Self {
+            max_requests,
+            period: Duration::from_secs(60 * 60),
+        }
+    }
+
+    pub fn per_day(max_requests: u32) -> Self {
+        Self {
+            max_requests,
+            period: Duration::from_secs(24 * 60 * 60),
+        }
+    }
+}
+
+/// Settings for `CircuitBreakerClient`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct CircuitBreakerConfig {
+    /// Number of consecutive failures, after which circuit opens.
+    pub failure_threshold: u32,
+    /// How long circuit stays open before trial request is allowed.
+    pub cooldown: Duration,
+    /// Optional limit of sent requests.
+    pub budget: Option<RequestBudget>,
+}
+
+impl Default for CircuitBreakerConfig {
+    fn default() -> Self {
+        Self {
+            failure_threshold: 5,
+            cooldown: Duration::from_secs(10 * 60),
+            budget: None,
+        }
+    }
+}
+
+/// Error, returned by `CircuitBreakerClient` without sending request.
+#[derive(Debug, Clone, PartialEq, thiserror::Error)]
+pub enum CircuitBreakerError {
+    /// Circuit is open after failures. Zero `retry_after` means that trial
+    /// request is in progress.
+    #[error("Circuit is open, retry after {retry_after:?}")]
+    Open { retry_after: Duration },
+
+    #[error("Request budget is exhausted, retry after {retry_after:?}")]
+    BudgetExhausted { retry_after: Duration },
+}
+
+/// State of `CircuitBreakerClient`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum CircuitState {
+    /// Requests are sent.
+    Closed,
+    /// Requests fail fast until cooldown ends.
+    Open { retry_after: Duration },
+    /// Single trial request is allowed, its result closes or reopens circuit.
+    HalfOpen,
+}
+
+enum State {
+    Closed { consecutive_failures: u32 },
+    Open { until: Instant },
+    HalfOpen { probe_in_flight: bool },
+}
+
+struct Breaker {
+    state: State,
+    /// Times of requests, sent during budget period.
+    sent: VecDeque<Instant>,
+}
+
+/// `HttpsClient` wrapper, which stops sending requests after consecutive
+/// failures, so Cloudflare ban is not prolonged.
+///
+/// Failures are transport errors, transient statuses (see
+/// `is_transient_status`), 403 and Cloudflare challenge pages. After
+/// `failure_threshold` failures in a row circuit opens and requests fail with
+/// `CircuitBreakerError::Open`. After `cooldown` single trial request is sent:
+/// success closes circuit, failure opens it again. Optional budget limits
+/// number of requests per period (`CircuitBreakerError::BudgetExhausted`).
+pub struct CircuitBreakerClient<C: HttpsClient> {
+    client: C,
+    config: CircuitBreakerConfig,
+    breaker: Mutex<Breaker>,
+}
+
+impl<C: HttpsClient> CircuitBreakerClient<C> {
+    /// Wrap client with default settings.
+    pub fn new(client: C) -> Self {
+        Self::with_config(client, CircuitBreakerConfig::default())
+    }
+
+    /// Wrap client with provided settings.
+    pub fn with_config(client: C, config: CircuitBreakerConfig) -> Self {
+        let breaker = Breaker {
+            state: State::Closed {
+                consecutive_failures: 0,
+            },
+            sent: VecDeque::new(),
+        };
+        Self {
+            client,
+            config,
+            breaker: Mutex::new(breaker),
+        }
+    }
+
+    /// Get reference to wrapped client.
+    pub fn inner(&self) -> &C {
+        &self.client
+    }
+
+    pub fn state(&self) -> CircuitState {
+        let breaker = self.lock();
+        match breaker.state {
+            State::Closed { .. } => CircuitState::Closed,
+            State::Open { until } => match until.checked_duration_since(Instant::now()) {
+                Some(retry_after) => CircuitState::Open { retry_after },
+                None => CircuitState::HalfOpen,
+            },
+            State::HalfOpen { .. } => CircuitState::HalfOpen,
+        }
+    }
+
+    /// Close circuit, forgetting failures. Budget is not reset.
+    pub fn reset(&self) {
+        self.lock().state = State::Closed {
+            consecutive_failures: 0,
+        };
+    }
+
+    fn lock(&self) -> MutexGuard<'_, Breaker> {
+        self.breaker.lock().unwrap_or_else(|err| err.into_inner())
+    }
+
+    /// Check whether request can be sent and account it.
+    fn acquire(&self) -> Result<(), CircuitBreakerError> {
+        let mut breaker = self.lock();
+        let now = Instant::now();
+
+        match breaker.state {
+            State::Closed { .. } => {}
+            State::Open { until } if until > now => {
+                return Err(CircuitBreakerError::Open {
+                    retry_after: until - now,
+                })
+            }
+            State::Open { .. }
+            | State::HalfOpen {
+                probe_in_flight: false,
+            } => {}
+            State::HalfOpen {
+                probe_in_flight: true,
+            } => {
+                return Err(CircuitBreakerError::Open {
+                    retry_after: Duration::from_secs(0),
+                })
+            }
+        }
+
+        if let Some(budget) = &self.config.budget {
+            while breaker
+                .sent
+                .front()
+                .is_some_and(|sent| now.duration_since(*sent) >= budget.period)
+            {
+                breaker.sent.pop_front();
+            }
+            if breaker.sent.len() >= budget.max_requests as usize {
+                let oldest = breaker.sent.front().copied().unwrap_or(now);
+                return Err(CircuitBreakerError::BudgetExhausted {
+                    retry_after: (oldest + budget.period).saturating_duration_since(now),
+                });
+            }
+            breaker.sent.push_back(now);
+        }
+
+        if matches!(breaker.state, State::Open { .. } | State::HalfOpen { .. }) {
+            breaker.state = State::HalfOpen {
+                probe_in_flight: true,
+            };
+        }
+        Ok(())
+    }
+
+    fn report(&self, success: bool) {
+        let mut breaker = self.lock();
+        breaker.state = match (&breaker.state, success) {
+            (_, true) => State::Closed {
+                consecutive_failures: 0,
+            },
+            (
+                State::Closed {
+                    consecutive_failures,
+                },
+                false,
+            ) if consecutive_failures + 1 < self.config.failure_threshold.max(1) => State::Closed {
+                consecutive_failures: consecutive_failures + 1,
+            },
+            (_, false) => State::Open {
+                until: Instant::now() + self.config.cooldown,
+            },
+        };
+    }
+}
+
+/// Check whether response means that HLTV is unavailable or blocks us.
+fn is_failure(response: &Response) -> bool {
+    is_transient_status(response.status)
+        || response.status == 403
+        || detect_block(&response.body).is_some()
+}
+
+impl<C: HttpsClient> HttpsClient for CircuitBreakerClient<C> {
+    fn get(&self, url: &str) -> Result<String, Box<dyn Error>> {
+        get_via_execute(self, url)
+    }
+
+    fn execute(&self, request: &Request) -> Result<Response, Box<dyn Error>> {
+        self.acquire()?;
+        let res = self.client.execute(request);
+        self.report(res.as_ref().is_ok_and(|response| !is_failure(response)));
+        res
+    }
+
+    fn is_conditional(&self) -> bool {
+        self.client.is_conditional()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::cell::Cell;
+
+    use super::*;
+
+    /// Client, which fails while `failing` is set.
+    struct SwitchClient {
+        failing: Cell<bool>,
+        calls: Cell<u32>,
+    }
+
+    impl HttpsClient for SwitchClient {
+        fn get(&self, _url: &str) -> Result<String, Box<dyn Error>> {
+            self.calls.set(self.calls.get() + 1);
+            if self.failing.get() {
+                Err(Box::new(std::io::Error::from(
+                    std::io::ErrorKind::ConnectionReset,
+                )))
+            } else {
+                Ok("<html></html>".into())
+            }
+        }
+    }
+
+    fn client(config: CircuitBreakerConfig) -> CircuitBreakerClient<SwitchClient> {
+        CircuitBreakerClient::with_config(
+            SwitchClient {
+                failing: Cell::new(false),
+                calls: Cell::new(0),
+            },
+            config,
+        )
+    }
+
+    fn open_error(err: Box<dyn Error>) -> CircuitBreakerError {
+        err.downcast_ref::<CircuitBreakerError>().unwrap().clone()
+    }
+
+    #[test]
+    fn open_and_recover() {
+        let client = client(CircuitBreakerConfig {
+            failure_threshold: 2,
+            cooldown: Duration::from_millis(50),
+            budget: None,
+        });
+        client.inner().failing.set(true);
+
+        assert!(client.get("/").is_err());
+        assert_eq!(client.state(), CircuitState::Closed);
+        assert!(client.get("/").is_err());
+        assert!(matches!(client.state(), CircuitState::Open { .. }));
+
+        // Fails fast without calling client
+        let err = open_error(client.get("/").unwrap_err());
+        assert!(
+            matches!(err, CircuitBreakerError::Open { retry_after } if retry_after > Duration::from_millis(0))
+        );
+        assert_eq!(client.inner().calls.get(), 2);
+
+        // Failed trial request opens circuit again
+        std::thread::sleep(Duration::from_millis(60));
+        assert_eq!(client.state(), CircuitState::HalfOpen);
+        assert!(client.get("/").is_err());
+        assert_eq!(client.inner().calls.get(), 3);
+        assert!(matches!(client.state(), CircuitState::Open { .. }));
+
+        // Successful trial request closes circuit
+        std::thread::sleep(Duration::from_millis(60));
+        client.inner().failing.set(false);
+        assert!(client.get("/").is_ok());
+        assert_eq!(client.state(), CircuitState::Closed);
+    }
+
+    #[test]
+    fn success_resets_failures() {
+        let client = client(CircuitBreakerConfig {
+            failure_threshold: 2,
+            ..CircuitBreakerConfig::default()
+        });
+        for _ in 0..3 {
+            client.inner().failing.set(true);
+            assert!(client.get("/").is_err());
+            client.inner().failing.set(false);
+            assert!(client.get("/").is_ok());
+        }
+        assert_eq!(client.state(), CircuitState::Closed);
+    }
+
+    #[test]
+    fn budget() {
+        let client = client(CircuitBreakerConfig {
+            budget: Some(RequestBudget {
+                max_requests: 2,
+                period: Duration::from_millis(50),
+            }),
+            ..CircuitBreakerConfig::default()
+        });
+
+        assert!(client.get("/").is_ok());
+        assert!(client.get("/").is_ok());
+        assert!(matches!(
+            open_error(client.get("/").unwrap_err()),
+            CircuitBreakerError::BudgetExhausted { .. }
+        ));
+        assert_eq!(client.inner().calls.get(), 2);
+
+        std::thread::sleep(Duration::from_millis(60));
+        assert!(client.get("/").is_ok());
+    }
+
+    #[test]
+    fn cloudflare_page_is_failure() {
+        assert!(is_failure(&Response::ok(
+            "/",
+            "<title>Just a moment...</title>"
+        )));
+        assert!(!is_failure(&Response::ok("/", "<html></html>")));
+    }
+}
@@ -0,0 +1,361 @@
+use std::{
+    error::Error,
+    fs, io,
+    path::PathBuf,
+    process,
+    sync::atomic::{AtomicU64, Ordering},
+    time::{Duration, SystemTime},
+};
+
+use super::{
+    detect_block, fnv1a, get_via_execute, url_path, HttpsClient, Method, Request, Response,
+};
+
+/// How long cached response stays valid.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum CacheTtl {
+    /// Do not cache responses.
+    NoCache,
+    /// Cache responses for provided time.
+    Expires(Duration),
+    /// Cache responses forever.
+    Forever,
+}
+
+/// Counter of temporary files, so concurrent writes do not share one.
+static TMP_COUNTER: AtomicU64 = AtomicU64::new(0);
+
+/// `HttpsClient` wrapper, which stores response bodies on disk, keyed by URL,
+/// request headers and cookies.
+///
+/// TTL is selected by the longest matching URL path prefix (see
+/// `CachingClient::with_ttl`), falling back to default TTL. Only successful
+/// responses to GET requests are cached, headers are not stored. Cloudflare
+/// challenge pages (see `detect_block`) are not cached, even if their status
+/// is successful. Failures to write cache are ignored, since response itself
+/// is valid.
+pub struct CachingClient<C: HttpsClient> {
+    client: C,
+    dir: PathBuf,
+    rules: Vec<(String, CacheTtl)>,
+    default_ttl: CacheTtl,
+}
+
+impl<C: HttpsClient> CachingClient<C> {
+    /// Wrap client with cache, stored in provided directory (created if
+    /// missing). Responses are not cached until TTLs are configured.
+    pub fn new<P: Into<PathBuf>>(client: C, dir: P) -> io::Result<Self> {
+        let dir = dir.into();
+        fs::create_dir_all(&dir)?;
+        Ok(Self {
+            client,
+            dir,
+            rules: Vec::new(),
+            default_ttl: CacheTtl::NoCache,
+        })
+    }
+
+    /// Set TTL for URLs, which path starts with `path_prefix` (like
+    /// `/news/archive/` or `/matches`).
+    pub fn with_ttl<P: Into<String>>(mut self, path_prefix: P, ttl: CacheTtl) -> Self {
+        self.rules.push((path_prefix.into(), ttl));
+        self
+    }
+
+    /// Set TTL for URLs, which do not match any prefix.
+    pub fn with_default_ttl(mut self, ttl: CacheTtl) -> Self {
+        self.default_ttl = ttl;
+        self
+    }
+
+    /// Get reference to wrapped client.
+    pub fn inner(&self) -> &C {
+        &self.client
+    }
+
+    /// Remove cached responses for URL (with any headers and cookies).
+    pub fn invalidate(&self, url: &str) -> io::Result<()> {
+        let prefix = format!("{:016x}-", fnv1a(url.as_bytes()));
+        self.remove_entries(|name| name.starts_with(&prefix))
+    }
+
+    /// Remove all cached responses.
+    pub fn clear(&self) -> io::Result<()> {
+        self.remove_entries(|_| true)
+    }
+
+    fn remove_entries<F: Fn(&str) -> bool>(&self, filter: F) -> io::Result<()> {
+        for entry in fs::read_dir(&self.dir)? {
+            let path = entry?.path();
+            let matches = path.extension().is_some_and(|ext| ext == "cache")
+                && path
+                    .file_name()
+                    .and_then(|name| name.to_str())
+                    .is_some_and(&filter);
+            if matches {
+                match fs::remove_file(path) {
+                    Err(err) if err.kind() != io::ErrorKind::NotFound => return Err(err),
+                    _ => {}
+                }
+            }
+        }
+        Ok(())
+    }
+
+    fn ttl(&self, url: &str) -> CacheTtl {
+        let path = url_path(url);
+        self.rules
+            .iter()
+            .filter(|(prefix, _)| path.starts_with(prefix.as_str()))
+            .max_by_key(|(prefix, _)| prefix.len())
+            .map(|(_, ttl)| *ttl)
+            .unwrap_or(self.default_ttl)
+    }
+
+    fn entry_path(&self, request: &Request) -> PathBuf {
+        self.dir.join(format!(
+            "{:016x}-{:016x}.cache",
+            fnv1a(request.url.as_bytes()),
+            fnv1a(variant(request).as_bytes())
+        ))
+    }
+
+    /// Read cached body, if it exists and is not expired.
+    fn read(&self, request: &Request, ttl: CacheTtl) -> Option<String> {
+        let path = self.entry_path(request);
+        if let CacheTtl::Expires(ttl) = ttl {
+            let modified = fs::metadata(&path).and_then(|m| m.modified()).ok()?;
+            let age = SystemTime::now()
+                .duration_since(modified)
+                .unwrap_or_default();
+            if age > ttl {
+                return None;
+            }
+        }
+        let content = fs::read_to_string(&path).ok()?;
+        // First line is key, it protects from hash collisions
+        let (cached_key, body) = content.split_at(content.find('\n')?);
+        if cached_key == key(request) {
+            Some(body[1..].to_string())
+        } else {
+            None
+        }
+    }
+
+    fn write(&self, request: &Request, body: &str) -> io::Result<()> {
+        let path = self.entry_path(request);
+        let tmp_path = path.with_extension(format!(
+            "{}-{}.tmp",
+            process::id(),
+            TMP_COUNTER.fetch_add(1, Ordering::Relaxed)
+        ));
+        let res = fs::write(&tmp_path, format!("{}\n{}", key(request), body))
+            .and_then(|_| fs::rename(&tmp_path, path));
+        if res.is_err() {
+            let _ = fs::remove_file(tmp_path);
+        }
+        res
+    }
+}
+
+impl<C: HttpsClient> HttpsClient for CachingClient<C> {
+    fn get(&self, url: &str) -> Result<String, Box<dyn Error>> {
+        get_via_execute(self, url)
+    }
+
+    fn execute(&self, request: &Request) -> Result<Response, Box<dyn Error>> {
+        let url = &request.url;
+        let ttl = self.ttl(url);
+        if ttl == CacheTtl::NoCache || request.method != Method::Get {
+            return self.client.execute(request);
+        }
+        if let Some(body) = self.read(request, ttl) {
+            return Ok(Response::ok(url.as_str(), body));
+        }
+        let response = self.client.execute(request)?;
+        if response.is_success() && detect_block(&response.body).is_none() {
+            let _ = self.write(request, &response.body);
+        }
+        Ok(response)
+    }
+
+    fn is_conditional(&self) -> bool {
+        self.client.is_conditional()
+    }
+}
+
+/// Headers and cookies of request in single line, sorted by name.
+fn variant(request: &Request) -> String {
+    let mut parts = request
+        .headers
+        .iter()
+        .map(|(name, value)| format!("{}: {}", name.to_ascii_lowercase(), value))
+        .collect::<Vec<_>>();
+    parts.sort();
+    if let Some(cookie) = request.cookies.to_header() {
+        parts.push(format!("cookie: {}", cookie));
+    }
+    parts.join("\t").replace(['\r', '\n'], " ")
+}
+
+/// Cache key, stored in first line of entry.
+fn key(request: &Request) -> String {
+    format!(
+        "{}\t{}",
+        request.url.replace(['\r', '\n'], " "),
+        variant(request)
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{cell::Cell, path::Path};
+
+    use super::*;
+
+    struct CountingClient {
+        calls: Cell<u32>,
+    }
+
+    impl HttpsClient for CountingClient {
+        fn get(&self, url: &str) -> Result<String, Box<dyn Error>> {
+            self.calls.set(self.calls.get() + 1);
+            if url.ends_with("/fail") {
+                Err("failed".into())
+            } else {
+                Ok(format!("{}\nbody #{}", url, self.calls.get()))
+            }
+        }
+    }
+
+    fn client(dir: &Path) -> CachingClient<CountingClient> {
+        CachingClient::new(
+            CountingClient {
+                calls: Cell::new(0),
+            },
+            dir,
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn cache_forever() {
+        let dir = tempfile::tempdir().unwrap();
+        let client = client(dir.path()).with_ttl("/news/archive/", CacheTtl::Forever);
+        let url = "https://www.hltv.org/news/archive/2020/november";
+
+        let first = client.get(url).unwrap();
+        assert_eq!(client.get(url).unwrap(), first);
+        assert_eq!(client.inner().calls.get(), 1);
+
+        // Cache survives client restart
+        let client = self::client(dir.path()).with_ttl("/news/", CacheTtl::Forever);
+        assert_eq!(client.get(url).unwrap(), first);
+        assert_eq!(client.inner().calls.get(), 0);
+    }
+
+    #[test]
+    fn cache_expires() {
+        let dir = tempfile::tempdir().unwrap();
+        let client = client(dir.path())
+            .with_ttl("/", CacheTtl::Forever)
+            .with_ttl("/matches", CacheTtl::Expires(Duration::from_millis(50)));
+        let url = "https://www.hltv.org/matches";
+
+        client.get(url).unwrap();
+        client.get(url).unwrap();
+        assert_eq!(client.inner().calls.get(), 1);
+        std::thread::sleep(Duration::from_millis(100));
+        client.get(url).unwrap();
+        assert_eq!(client.inner().calls.get(), 2);
+    }
+
+    #[test]
+    fn no_cache_by_default() {
+        let dir = tempfile::tempdir().unwrap();
+        let client = client(dir.path()).with_ttl("/news/", CacheTtl::Forever);
+
+        client.get("https://www.hltv.org/matches").unwrap();
+        client.get("https://www.hltv.org/matches").unwrap();
+        assert_eq!(client.inner().calls.get(), 2);
+    }
+
+    #[test]
+    fn errors_not_cached() {
+        let dir = tempfile::tempdir().unwrap();
+        let client = client(dir.path()).with_default_ttl(CacheTtl::Forever);
+
+        assert!(client.get("https://www.hltv.org/fail").is_err());
+        assert!(client.get("https://www.hltv.org/fail").is_err());
+        assert_eq!(client.inner().calls.get(), 2);
+    }
+
+    #[test]
+    fn invalidate_and_clear() {
+        let dir = tempfile::tempdir().unwrap();
+        let client = client(dir.path()).with_default_ttl(CacheTtl::Forever);
+
+        client.get("https://www.hltv.org/a").unwrap();
+        client.get("https://www.hltv.org/b").unwrap();
+        client.invalidate("https://www.hltv.org/a").unwrap();
+        client.get("https://www.hltv.org/a").unwrap();
+        client.get("https://www.hltv.org/b").unwrap();
+        assert_eq!(client.inner().calls.get(), 3);
+
+        client.clear().unwrap();
+        client.get("https://www.hltv.org/b").unwrap();
+        assert_eq!(client.inner().calls.get(), 4);
+    }
+
+    #[test]
+    fn keyed_by_headers_and_cookies() {
+        let dir = tempfile::tempdir().unwrap();
+        let client = client(dir.path()).with_default_ttl(CacheTtl::Forever);
+        let url = "https://www.hltv.org/matches";
+
+        client.execute(&Request::get(url)).unwrap();
+        client
+            .execute(&Request::get(url).with_cookie("session", "1"))
+            .unwrap();
+        client
+            .execute(&Request::get(url).with_header("User-Agent", "test"))
+            .unwrap();
+        client
+            .execute(&Request::get(url).with_cookie("session", "1"))
+            .unwrap();
+        assert_eq!(client.inner().calls.get(), 3);
+
+        client.invalidate(url).unwrap();
+        client.execute(&Request::get(url)).unwrap();
+        client
+            .execute(&Request::get(url).with_cookie("session", "1"))
+            .unwrap();
+        assert_eq!(client.inner().calls.get(), 5);
+    }
+
+    #[test]
+    fn concurrent_writes() {
+        let dir = tempfile::tempdir().unwrap();
+        struct NoClient;
+        impl HttpsClient for NoClient {
+            fn get(&self, _url: &str) -> Result<String, Box<dyn Error>> {
+                Err("unreachable".into())
+            }
+        }
+        let cache = CachingClient::new(NoClient, dir.path()).unwrap();
+        let request = Request::get("https://www.hltv.org/matches");
+        let bodies = (0..8)
+            .map(|i| i.to_string().repeat(10_000))
+            .collect::<Vec<_>>();
+
+        std::thread::scope(|scope| {
+            for body in &bodies {
+                let (cache, request) = (&cache, &request);
+                scope.spawn(move || cache.write(request, body).unwrap());
+            }
+        });
+        let body = cache.read(&request, CacheTtl::Forever).unwrap();
+        assert!(bodies.contains(&body));
+        assert_eq!(fs::read_dir(dir.path()).unwrap().count(), 1);
+    }
+}
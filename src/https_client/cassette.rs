@@ -0,0 +1,295 @@
+use std::{
+    error::Error,
+    fs, io,
+    path::{Path, PathBuf},
+};
+
+use super::{fnv1a, get_via_execute, HttpsClient, Method, Request, Response};
+
+/// Recorded response.
+///
+/// Cassette is stored in text file, which starts with headers (`url`, and
+/// either `status` with optional `header` lines or `error`), followed by
+/// empty line and response body.
+#[derive(Debug, PartialEq)]
+enum Cassette {
+    Response(Response),
+    Error(String),
+}
+
+impl Cassette {
+    fn from_result(res: &Result<Response, Box<dyn Error>>) -> Self {
+        match res {
+            Ok(response) => Self::Response(response.clone()),
+            Err(err) => Self::Error(err.to_string().replace('\n', " ")),
+        }
+    }
+
+    fn into_result(self) -> Result<Response, Box<dyn Error>> {
+        match self {
+            Self::Response(response) => Ok(response),
+            Self::Error(err) => Err(err.into()),
+        }
+    }
+
+    fn write(&self, path: &Path, url: &str) -> io::Result<()> {
+        let content = match self {
+            Self::Response(response) => {
+                let headers = response
+                    .headers
+                    .iter()
+                    .map(|(name, value)| format!("header: {}: {}\n", name, value))
+                    .collect::<String>();
+                format!(
+                    "url: {}\nstatus: {}\n{}\n{}",
+                    url, response.status, headers, response.body
+                )
+            }
+            Self::Error(err) => format!("url: {}\nerror: {}\n\n", url, err),
+        };
+        fs::write(path, content)
+    }
+
+    fn read(path: &Path, url: &str) -> io::Result<Self> {
+        let invalid = |text: &str| {
+            io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!("{} in cassette {}", text, path.display()),
+            )
+        };
+
+        let content = fs::read_to_string(path)?;
+        let (head, body) = content
+            .split_once("\n\n")
+            .ok_or_else(|| invalid("No headers"))?;
+        let mut status = None;
+        let mut headers = Vec::new();
+        for line in head.lines() {
+            let (key, value) = line
+                .split_once(": ")
+                .ok_or_else(|| invalid("Malformed header"))?;
+            match key {
+                "url" if value != url => return Err(invalid("URL mismatch")),
+                "status" => status = Some(value.parse().map_err(|_| invalid("Malformed status"))?),
+                "header" => {
+                    let (name, value) = value
+                        .split_once(": ")
+                        .ok_or_else(|| invalid("Malformed response header"))?;
+                    headers.push((name.to_string(), value.to_string()));
+                }
+                "error" => return Ok(Self::Error(value.into())),
+                _ => {}
+            }
+        }
+        let status = status.ok_or_else(|| invalid("No status or error"))?;
+        Ok(Self::Response(Response {
+            url: url.into(),
+            status,
+            headers,
+            body: body.into(),
+        }))
+    }
+}
+
+/// Path to cassette file for request: readable part of URL with hash suffix.
+/// Non-GET requests are additionally prefixed with method.
+fn cassette_path(dir: &Path, request: &Request) -> PathBuf {
+    let url = request.url.as_str();
+    let readable = url
+        .split("://")
+        .last()
+        .unwrap_or_default()
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() { c } else { '_' })
+        .take(100)
+        .collect::<String>();
+    let method = match request.method {
+        Method::Get => String::new(),
+        method => format!("{}-", method.as_str()),
+    };
+    dir.join(format!(
+        "{}{}-{:08x}.cassette",
+        method,
+        readable,
+        fnv1a(url.as_bytes()) as u32
+    ))
+}
+
+/// `HttpsClient` wrapper, which saves every response (including errors) to
+/// cassette in provided directory, so it can be served later by
+/// `ReplayClient`.
+pub struct RecordingClient<C: HttpsClient> {
+    client: C,
+    dir: PathBuf,
+}
+
+impl<C: HttpsClient> RecordingClient<C> {
+    /// Wrap client, recording cassettes into provided directory (created if
+    /// missing).
+    pub fn new<P: Into<PathBuf>>(client: C, dir: P) -> io::Result<Self> {
+        let dir = dir.into();
+        fs::create_dir_all(&dir)?;
+        Ok(Self { client, dir })
+    }
+
+    /// Get reference to wrapped client.
+    pub fn inner(&self) -> &C {
+        &self.client
+    }
+}
+
+impl<C: HttpsClient> HttpsClient for RecordingClient<C> {
+    fn get(&self, url: &str) -> Result<String, Box<dyn Error>> {
+        get_via_execute(self, url)
+    }
+
+    fn execute(&self, request: &Request) -> Result<Response, Box<dyn Error>> {
+        let res = self.client.execute(request);
+        Cassette::from_result(&res).write(&cassette_path(&self.dir, request), &request.url)?;
+        res
+    }
+
+    fn is_conditional(&self) -> bool {
+        self.client.is_conditional()
+    }
+}
+
+/// `HttpsClient`, which serves responses from cassettes, recorded by
+/// `RecordingClient`. Request without cassette fails.
+pub struct ReplayClient {
+    dir: PathBuf,
+}
+
+impl ReplayClient {
+    /// Build client, reading cassettes from provided directory.
+    pub fn new<P: Into<PathBuf>>(dir: P) -> Self {
+        Self { dir: dir.into() }
+    }
+}
+
+impl HttpsClient for ReplayClient {
+    fn get(&self, url: &str) -> Result<String, Box<dyn Error>> {
+        get_via_execute(self, url)
+    }
+
+    fn execute(&self, request: &Request) -> Result<Response, Box<dyn Error>> {
+        let url = &request.url;
+        let path = cassette_path(&self.dir, request);
+        match Cassette::read(&path, url) {
+            Ok(cassette) => cassette.into_result(),
+            Err(err) if err.kind() == io::ErrorKind::NotFound => {
+                Err(format!("No cassette for {} (expected {})", url, path.display()).into())
+            }
+            Err(err) => Err(Box::new(err)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::*;
+    use crate::https_client::HttpStatusError;
+
+    struct StaticClient;
+
+    impl HttpsClient for StaticClient {
+        fn get(&self, url: &str) -> Result<String, Box<dyn Error>> {
+            match url {
+                "https://www.hltv.org/404" => Err(Box::new(HttpStatusError {
+                    status: 404,
+                    url: url.into(),
+                    retry_after: None,
+                    body: "Not found".into(),
+                })),
+                "https://www.hltv.org/fail" => Err("connection\nfailed".into()),
+                _ => Ok(format!("<html>\n\n{}\n</html>", url)),
+            }
+        }
+    }
+
+    #[test]
+    fn record_and_replay() {
+        let dir = tempfile::tempdir().unwrap();
+        let recorder = RecordingClient::new(StaticClient, dir.path()).unwrap();
+        let replay = ReplayClient::new(dir.path());
+        let urls = [
+            "https://www.hltv.org/",
+            "https://www.hltv.org/results?offset=100",
+            "https://www.hltv.org/404",
+            "https://www.hltv.org/fail",
+        ];
+
+        for url in urls.iter() {
+            let recorded = recorder.get(url).map_err(|err| err.to_string());
+            let replayed = replay.get(url).map_err(|err| err.to_string());
+            assert_eq!(recorded.map_err(|err| err.replace('\n', " ")), replayed);
+        }
+
+        assert!(replay
+            .get("https://www.hltv.org/404")
+            .unwrap_err()
+            .downcast_ref::<HttpStatusError>()
+            .is_some());
+    }
+
+    #[test]
+    fn replay_missing() {
+        let dir = tempfile::tempdir().unwrap();
+        assert!(ReplayClient::new(dir.path())
+            .get("https://www.hltv.org/")
+            .is_err());
+    }
+
+    #[test]
+    fn cassette_paths() {
+        let dir = Path::new("/cassettes");
+        let request = Request::get("https://www.hltv.org/results?offset=100");
+        let path = cassette_path(dir, &request);
+        assert!(path
+            .to_str()
+            .unwrap()
+            .starts_with("/cassettes/www_hltv_org_results_offset_100-"));
+        assert_ne!(
+            path,
+            cassette_path(
+                dir,
+                &Request::get("https://www.hltv.org/results_offset=100")
+            )
+        );
+
+        let head = Request {
+            method: Method::Head,
+            ..request
+        };
+        assert!(cassette_path(dir, &head)
+            .to_str()
+            .unwrap()
+            .starts_with("/cassettes/HEAD-www_hltv_org_results_offset_100-"));
+    }
+
+    #[test]
+    fn response_headers() {
+        let dir = tempfile::tempdir().unwrap();
+        let url = "https://www.hltv.org/busy";
+        let response = Response {
+            url: url.into(),
+            status: 503,
+            headers: vec![
+                ("Retry-After".into(), "30".into()),
+                ("Content-Type".into(), "text/html; charset=utf-8".into()),
+            ],
+            body: "\nbusy\n\n".into(),
+        };
+        let request = Request::get(url);
+        let path = cassette_path(dir.path(), &request);
+        Cassette::Response(response.clone())
+            .write(&path, url)
+            .unwrap();
+
+        let replayed = ReplayClient::new(dir.path()).execute(&request).unwrap();
+        assert_eq!(replayed, response);
+        assert_eq!(replayed.retry_after(), Some(Duration::from_secs(30)));
+    }
+}
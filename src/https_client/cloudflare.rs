@@ -0,0 +1,158 @@
+/// Kind of page, which Cloudflare serves instead of requested one.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum BlockKind {
+    /// JS challenge ("Checking your browser", "Just a moment...").
+    JsChallenge,
+    /// Interactive challenge (captcha).
+    Captcha,
+    /// Access denied, for example by firewall rule (error 1020).
+    AccessDenied,
+    /// Rate limit exceeded (error 1015).
+    RateLimited,
+}
+
+/// Markers of Cloudflare pages. Captcha goes before JS challenge, since
+/// captcha pages usually contain JS challenge markers too, and error pages go
+/// before captcha, since they share title with it.
+///
+/// Phrases, which may be quoted in news or comments, are matched only in
+/// `<title>`. Other markers are Cloudflare-specific markup, matched anywhere.
+struct Markers {
+    kind: BlockKind,
+    /// Prefixes of plain text bodies, like "error code: 1015".
+    plain: &'static [&'static str],
+    title: &'static [&'static str],
+    markup: &'static [&'static str],
+}
+
+const BLOCK_MARKERS: &[Markers] = &[
+    Markers {
+        kind: BlockKind::RateLimited,
+        plain: &["error code: 1015"],
+        title: &["You are being rate limited"],
+        markup: &[r#"<span class="cf-error-code">1015</span>"#],
+    },
+    Markers {
+        kind: BlockKind::AccessDenied,
+        plain: &["error code: 1020"],
+        title: &["used Cloudflare to restrict access"],
+        markup: &[
+            r#"<span class="cf-error-code">1020</span>"#,
+            "cf-error-details",
+        ],
+    },
+    Markers {
+        kind: BlockKind::Captcha,
+        plain: &[],
+        title: &["Attention Required! | Cloudflare"],
+        markup: &["cf_captcha_kind", "cf-captcha-container"],
+    },
+    Markers {
+        kind: BlockKind::JsChallenge,
+        plain: &[],
+        title: &["Just a moment..."],
+        markup: &["cf-browser-verification", "jschl_vc", "cf_chl_opt"],
+    },
+];
+
+/// Get text of `<title>` element.
+fn title(page: &str) -> Option<&str> {
+    // ASCII lowercase keeps byte offsets
+    let lowercase = page.to_ascii_lowercase();
+    let tag = lowercase.find("<title")?;
+    let start = tag + lowercase[tag..].find('>')? + 1;
+    let end = start + lowercase[start..].find("</title>")?;
+    Some(page[start..end].trim())
+}
+
+/// Check whether page is Cloudflare challenge or block page.
+pub fn detect_block(page: &str) -> Option<BlockKind> {
+    let plain = page.trim_start();
+    let title = title(page);
+    BLOCK_MARKERS
+        .iter()
+        .find(|markers| {
+            markers.plain.iter().any(|marker| plain.starts_with(marker))
+                || title.is_some_and(|title| markers.title.iter().any(|m| title.contains(m)))
+                || markers.markup.iter().any(|marker| page.contains(marker))
+        })
+        .map(|markers| markers.kind)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const JS_CHALLENGE: &str = r#"
+<!DOCTYPE HTML>
+<html lang="en-US">
+<head>
+  <title>Just a moment...</title>
+</head>
+<body>
+  <div id="cf-content">
+    <h1><span class="cf-no-screenreader">Checking your browser before accessing</span> hltv.org.</h1>
+  </div>
+  <form id="challenge-form" action="/?__cf_chl_jschl_tk__=abc" method="POST">
+    <input type="hidden" name="jschl_vc" value="0123456789abcdef"/>
+  </form>
+</body>
+</html>
+"#;
+
+    const CAPTCHA: &str = r#"
+<!DOCTYPE html>
+<html>
+<head><title>Attention Required! | Cloudflare</title></head>
+<body>
+  <div class="cf-captcha-container"></div>
+  <script>var cf_chl_opt = {};</script>
+</body>
+</html>
+"#;
+
+    const RATE_LIMITED: &str = r#"
+<!DOCTYPE html>
+<html>
+<head><title>Access denied | www.hltv.org used Cloudflare to restrict access</title></head>
+<body>
+  <div id="cf-error-details" class="cf-error-details-wrapper">
+    <h1><span class="cf-error-type">Error</span> <span class="cf-error-code">1015</span></h1>
+    <h2 class="cf-subheadline">You are being rate limited</h2>
+  </div>
+</body>
+</html>
+"#;
+
+    #[test]
+    fn detect() {
+        assert_eq!(detect_block(JS_CHALLENGE), Some(BlockKind::JsChallenge));
+        assert_eq!(detect_block(CAPTCHA), Some(BlockKind::Captcha));
+        assert_eq!(
+            detect_block("error code: 1020"),
+            Some(BlockKind::AccessDenied)
+        );
+        assert_eq!(
+            detect_block("error code: 1015"),
+            Some(BlockKind::RateLimited)
+        );
+        assert_eq!(detect_block(r#"<div class="results-holder"></div>"#), None);
+        assert_eq!(detect_block(RATE_LIMITED), Some(BlockKind::RateLimited));
+    }
+
+    #[test]
+    fn quoted_markers_are_not_blocks() {
+        let article = r#"
+<html>
+<head><title>Players complain about matchmaking | HLTV.org</title></head>
+<body>
+  <div class="newstext-con">
+    <p>Players saw "You are being rate limited" and "Sorry, you have been blocked"
+    messages, then "Just a moment..." page with "error code: 1015".</p>
+  </div>
+</body>
+</html>
+"#;
+        assert_eq!(detect_block(article), None);
+    }
+}
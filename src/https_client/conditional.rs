@@ -0,0 +1,182 @@
+use std::{collections::HashMap, error::Error, sync::Mutex};
+
+use super::{get_via_execute, HttpsClient, Method, Request, Response};
+
+/// Status of response to conditional request, when resource is not modified.
+pub const NOT_MODIFIED: u16 = 304;
+
+/// Values of `ETag` and `Last-Modified` headers of response.
+#[derive(Clone, Debug, Default, PartialEq)]
+struct Validators {
+    etag: Option<String>,
+    last_modified: Option<String>,
+}
+
+impl Validators {
+    fn from_response(response: &Response) -> Option<Self> {
+        let validators = Self {
+            etag: response.header("ETag").map(Into::into),
+            last_modified: response.header("Last-Modified").map(Into::into),
+        };
+        if validators == Self::default() {
+            None
+        } else {
+            Some(validators)
+        }
+    }
+
+    /// Add conditional headers to request.
+    fn apply(self, mut request: Request) -> Request {
+        if let Some(etag) = self.etag {
+            request = request.with_header("If-None-Match", etag);
+        }
+        if let Some(last_modified) = self.last_modified {
+            request = request.with_header("If-Modified-Since", last_modified);
+        }
+        request
+    }
+}
+
+/// Check whether request asks to skip revalidation (see
+/// `Request::unconditional`) or already has own validators.
+fn skip_revalidation(request: &Request) -> bool {
+    request.method != Method::Get
+        || request.is_unconditional()
+        || request.header("If-None-Match").is_some()
+        || request.header("If-Modified-Since").is_some()
+}
+
+/// `HttpsClient` wrapper, which stores validators (`ETag` and
+/// `Last-Modified`) of successful responses and sends them with subsequent
+/// requests to the same URL (as `If-None-Match` and `If-Modified-Since`).
+///
+/// If page is not modified, `execute` returns response with status 304
+/// (`NOT_MODIFIED`) and empty body. `get` never sends conditional requests,
+/// since it must return body. Unconditional requests (see
+/// `Request::unconditional`) are sent without validators too.
+pub struct ConditionalClient<C: HttpsClient> {
+    client: C,
+    validators: Mutex<HashMap<String, Validators>>,
+}
+
+impl<C: HttpsClient> ConditionalClient<C> {
+    pub fn new(client: C) -> Self {
+        Self {
+            client,
+            validators: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Get reference to wrapped client.
+    pub fn inner(&self) -> &C {
+        &self.client
+    }
+
+    /// Forget validators for URL, so next request is unconditional.
+    pub fn forget(&self, url: &str) {
+        self.lock().remove(url);
+    }
+
+    /// Forget all validators.
+    pub fn clear(&self) {
+        self.lock().clear();
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, HashMap<String, Validators>> {
+        self.validators
+            .lock()
+            .unwrap_or_else(|err| err.into_inner())
+    }
+}
+
+impl<C: HttpsClient> HttpsClient for ConditionalClient<C> {
+    fn get(&self, url: &str) -> Result<String, Box<dyn Error>> {
+        get_via_execute(self, url)
+    }
+
+    fn execute(&self, request: &Request) -> Result<Response, Box<dyn Error>> {
+        let validators = if skip_revalidation(request) {
+            None
+        } else {
+            self.lock().get(&request.url).cloned()
+        };
+        let response = match validators {
+            Some(validators) => self.client.execute(&validators.apply(request.clone()))?,
+            None => self.client.execute(request)?,
+        };
+        if request.method == Method::Get && response.is_success() {
+            match Validators::from_response(&response) {
+                Some(validators) => self.lock().insert(request.url.clone(), validators),
+                None => self.lock().remove(&request.url),
+            };
+        }
+        Ok(response)
+    }
+
+    fn is_conditional(&self) -> bool {
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::cell::RefCell;
+
+    use super::*;
+
+    /// Client, which serves page with ETag and records conditional headers.
+    #[derive(Default)]
+    struct EtagClient {
+        received: RefCell<Vec<Option<String>>>,
+    }
+
+    impl HttpsClient for EtagClient {
+        fn get(&self, url: &str) -> Result<String, Box<dyn Error>> {
+            crate::https_client::get_via_execute(self, url)
+        }
+
+        fn execute(&self, request: &Request) -> Result<Response, Box<dyn Error>> {
+            let if_none_match = request.header("If-None-Match").map(String::from);
+            self.received.borrow_mut().push(if_none_match.clone());
+            let mut response = Response::ok(request.url.as_str(), "<html></html>");
+            if if_none_match.as_deref() == Some("\"v1\"") {
+                response.status = NOT_MODIFIED;
+                response.body.clear();
+            }
+            response.headers.push(("ETag".into(), "\"v1\"".into()));
+            Ok(response)
+        }
+    }
+
+    #[test]
+    fn revalidate() {
+        let client = ConditionalClient::new(EtagClient::default());
+        let request = Request::get("https://www.hltv.org/matches");
+
+        assert_eq!(client.execute(&request).unwrap().status, 200);
+        assert_eq!(client.execute(&request).unwrap().status, NOT_MODIFIED);
+        // `get` must return body, so it is never conditional
+        assert_eq!(client.get(&request.url).unwrap(), "<html></html>");
+        let unconditional = request.clone().unconditional();
+        assert_eq!(client.execute(&unconditional).unwrap().status, 200);
+        client.forget(&request.url);
+        assert_eq!(client.execute(&request).unwrap().status, 200);
+
+        assert_eq!(
+            *client.inner().received.borrow(),
+            vec![None, Some("\"v1\"".into()), None, None, None]
+        );
+        assert!(client.is_conditional());
+        assert!(!client.inner().is_conditional());
+    }
+
+    #[test]
+    fn get_through_wrapper() {
+        let client = crate::ThrottledClient::new(ConditionalClient::new(EtagClient::default()));
+        let url = "https://www.hltv.org/matches";
+
+        assert_eq!(client.get(url).unwrap(), "<html></html>");
+        assert_eq!(client.get(url).unwrap(), "<html></html>");
+        assert_eq!(*client.inner().inner().received.borrow(), vec![None, None]);
+    }
+}
@@ -0,0 +1,280 @@
+use std::{error::Error, time::Duration};
+
+use attohttpc::header::{HeaderMap, HeaderName, CONTENT_LENGTH, COOKIE, USER_AGENT};
+
+use crate::https_client::{
+    check_content_length, get_via_execute, read_body, HttpsClient, LimitError, Method, Proxy,
+    ProxyError, ProxyScheme, Request, Response, DEFAULT_MAX_BODY_SIZE, DEFAULT_MAX_REDIRECTS,
+    DEFAULT_USER_AGENT,
+};
+
+/// Settings for `AttoHttpcImpl`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct AttoHttpcConfig {
+    /// Timeout for establishing connection.
+    pub connect_timeout: Duration,
+    /// Timeout for reading from socket.
+    pub read_timeout: Duration,
+    /// Maximal size of response body in bytes, `None` means unlimited.
+    pub max_body_size: Option<u64>,
+    /// Maximal number of followed redirects.
+    pub max_redirects: u32,
+}
+
+impl Default for AttoHttpcConfig {
+    fn default() -> Self {
+        Self {
+            connect_timeout: Duration::from_secs(30),
+            read_timeout: Duration::from_secs(30),
+            max_body_size: Some(DEFAULT_MAX_BODY_SIZE),
+            max_redirects: DEFAULT_MAX_REDIRECTS,
+        }
+    }
+}
+
+/// `HttpsClient` implementation for `attohttpc` crate.
+///
+/// Sends `DEFAULT_USER_AGENT`, unless request has own `User-Agent` header.
+/// Without explicit proxy, system proxy settings from `HTTP_PROXY` and
+/// `HTTPS_PROXY` environment variables are used.
+#[derive(Clone, Debug, Default)]
+pub struct AttoHttpcImpl {
+    config: AttoHttpcConfig,
+    proxy: Option<attohttpc::ProxySettings>,
+}
+
+impl AttoHttpcImpl {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Build client with provided settings.
+    pub fn with_config(config: AttoHttpcConfig) -> Self {
+        Self {
+            config,
+            proxy: None,
+        }
+    }
+
+    /// Build client with provided settings, which sends all requests through
+    /// proxy. Only HTTP and HTTPS proxies are supported.
+    pub fn with_proxy(config: AttoHttpcConfig, proxy: Proxy) -> Result<Self, ProxyError> {
+        proxy.check_scheme(&[ProxyScheme::Http, ProxyScheme::Https], "attohttpc")?;
+        let url = url::Url::parse(&proxy.url())
+            .map_err(|err| ProxyError::InvalidUrl(format!("{} ({})", proxy, err)))?;
+        let settings = attohttpc::ProxySettings::builder()
+            .http_proxy(url.clone())
+            .https_proxy(url)
+            .build();
+        Ok(Self {
+            config,
+            proxy: Some(settings),
+        })
+    }
+
+    fn send(&self, request: &Request) -> Result<Response, Box<dyn Error>> {
+        let mut builder = match request.method {
+            Method::Get => attohttpc::get(&request.url),
+            Method::Head => attohttpc::head(&request.url),
+        }
+        .header(USER_AGENT, DEFAULT_USER_AGENT)
+        .connect_timeout(self.config.connect_timeout)
+        .read_timeout(self.config.read_timeout)
+        .max_redirections(self.config.max_redirects);
+        if let Some(proxy) = &self.proxy {
+            builder = builder.proxy_settings(proxy.clone());
+        }
+        for (name, value) in &request.headers {
+            builder =
+                builder.try_header(HeaderName::from_bytes(name.as_bytes())?, value.as_str())?;
+        }
+        if let Some(cookie) = request.cookies.to_header() {
+            builder = builder.try_header(COOKIE, cookie)?;
+        }
+
+        let response = builder.send().map_err(|err| self.map_err(err))?;
+        let (status, headers, reader) = response.split();
+        let body = match request.method {
+            Method::Head => String::new(),
+            Method::Get => {
+                let content_length = headers
+                    .get(CONTENT_LENGTH)
+                    .and_then(|value| value.to_str().ok()?.parse().ok());
+                check_content_length(content_length, self.config.max_body_size)?;
+                read_body(reader, self.config.max_body_size)?
+            }
+        };
+        let status = status.as_u16();
+        let headers = convert_headers(&headers);
+        Ok(Response {
+            url: request.url.clone(),
+            status,
+            headers,
+            body,
+        })
+    }
+}
+
+fn convert_headers(headers: &HeaderMap) -> Vec<(String, String)> {
+    headers
+        .iter()
+        .filter_map(|(name, value)| Some((name.to_string(), value.to_str().ok()?.to_string())))
+        .collect()
+}
+
+impl AttoHttpcImpl {
+    /// Unwrap IO errors, so they can be classified by wrappers.
+    fn map_err(&self, err: attohttpc::Error) -> Box<dyn Error> {
+        match err.into_kind() {
+            // Read timeout is reported as `WouldBlock` on some platforms
+            attohttpc::ErrorKind::Io(err) if err.kind() == std::io::ErrorKind::WouldBlock => {
+                Box::new(std::io::Error::new(std::io::ErrorKind::TimedOut, err))
+            }
+            attohttpc::ErrorKind::Io(err) => Box::new(err),
+            attohttpc::ErrorKind::TooManyRedirections => Box::new(LimitError::TooManyRedirects {
+                limit: self.config.max_redirects,
+            }),
+            kind => Box::new(attohttpc::Error::from(kind)),
+        }
+    }
+}
+
+impl HttpsClient for AttoHttpcImpl {
+    fn get(&self, url: &str) -> Result<String, Box<dyn Error>> {
+        get_via_execute(self, url)
+    }
+
+    fn execute(&self, request: &Request) -> Result<Response, Box<dyn Error>> {
+        self.send(request)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::*;
+    use crate::https_client::{test_server, HttpStatusError};
+
+    #[test]
+    fn get() {
+        assert!(AttoHttpcImpl::new().get(&test_server::serve()).is_ok())
+    }
+
+    #[test]
+    #[ignore = "requires network access"]
+    fn get_ssl() {
+        assert!(AttoHttpcImpl::new().get("https://example.com").is_ok())
+    }
+
+    #[test]
+    fn get_err() {
+        let err = AttoHttpcImpl::new()
+            .get(&format!("{}/unknown", test_server::serve()))
+            .unwrap_err();
+        assert_eq!(err.downcast_ref::<HttpStatusError>().unwrap().status, 404);
+    }
+
+    #[test]
+    fn get_retry_after() {
+        let err = AttoHttpcImpl::new()
+            .get(&format!("{}/busy", test_server::serve()))
+            .unwrap_err();
+        assert_eq!(
+            err.downcast_ref::<HttpStatusError>().unwrap().retry_after,
+            Some(Duration::from_secs(7))
+        );
+    }
+
+    #[test]
+    #[ignore = "requires network access"]
+    fn get_ssl_err() {
+        assert!(AttoHttpcImpl::new()
+            .get("https://example.com/unknown")
+            .is_err())
+    }
+
+    #[test]
+    fn default_user_agent() {
+        let url = format!("{}/user-agent", test_server::serve());
+        assert_eq!(AttoHttpcImpl::new().get(&url).unwrap(), DEFAULT_USER_AGENT);
+    }
+
+    #[test]
+    fn execute_headers_and_cookies() {
+        let url = test_server::serve();
+        let client = AttoHttpcImpl::new();
+
+        let request = Request::get(format!("{}/user-agent", url)).with_header("User-Agent", "test");
+        assert_eq!(client.execute(&request).unwrap().body, "test");
+
+        let request = Request::get(format!("{}/cookie", url))
+            .with_cookie("timezone", "UTC")
+            .with_cookie("hideSpoilers", "1");
+        assert_eq!(
+            client.execute(&request).unwrap().body,
+            "hideSpoilers=1; timezone=UTC"
+        );
+
+        let response = client
+            .execute(&Request::get(format!("{}/busy", url)))
+            .unwrap();
+        assert_eq!(response.status, 503);
+        assert_eq!(response.header("retry-after"), Some("7"));
+    }
+
+    #[test]
+    fn limits() {
+        let url = test_server::serve();
+        let client = AttoHttpcImpl::with_config(AttoHttpcConfig {
+            read_timeout: Duration::from_millis(200),
+            max_body_size: Some(1024),
+            max_redirects: 2,
+            ..AttoHttpcConfig::default()
+        });
+
+        let err = client.get(&format!("{}/slow", url)).unwrap_err();
+        assert_eq!(
+            err.downcast_ref::<std::io::Error>().unwrap().kind(),
+            std::io::ErrorKind::TimedOut
+        );
+        let err = client.get(&format!("{}/large", url)).unwrap_err();
+        assert_eq!(
+            err.downcast_ref::<LimitError>(),
+            Some(&LimitError::BodyTooLarge { limit: 1024 })
+        );
+        let err = client.get(&format!("{}/redirect", url)).unwrap_err();
+        assert_eq!(
+            err.downcast_ref::<LimitError>(),
+            Some(&LimitError::TooManyRedirects { limit: 2 })
+        );
+        assert!(client.get(&url).is_ok());
+    }
+
+    #[test]
+    fn proxy() {
+        let proxy = test_server::serve().parse().unwrap();
+        let client = AttoHttpcImpl::with_proxy(AttoHttpcConfig::default(), proxy).unwrap();
+        assert_eq!(client.get("http://hltv.invalid/").unwrap(), "proxied");
+
+        // Settings are applied to proxied client
+        let config = AttoHttpcConfig {
+            max_body_size: Some(3),
+            ..AttoHttpcConfig::default()
+        };
+        let client = AttoHttpcImpl::with_proxy(config, test_server::serve().parse().unwrap());
+        let err = client.unwrap().get("http://hltv.invalid/").unwrap_err();
+        assert_eq!(
+            err.downcast_ref::<LimitError>(),
+            Some(&LimitError::BodyTooLarge { limit: 3 })
+        );
+
+        assert!(matches!(
+            AttoHttpcImpl::with_proxy(
+                AttoHttpcConfig::default(),
+                Proxy::new(ProxyScheme::Socks5, "127.0.0.1", 1080)
+            ),
+            Err(ProxyError::Unsupported { .. })
+        ));
+    }
+}
@@ -0,0 +1,6 @@
+#[cfg(feature = "attohttpc_client")]
+pub mod attohttpc_impl;
+#[cfg(feature = "reqwest_client")]
+pub mod reqwest_impl;
+#[cfg(feature = "ureq_client")]
+pub mod ureq_impl;
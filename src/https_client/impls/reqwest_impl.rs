@@ -0,0 +1,448 @@
+use std::{error::Error, time::Duration};
+
+use reqwest::header::{HeaderMap, COOKIE};
+
+use crate::https_client::{
+    check_content_length, get_via_execute, read_body, AsyncHttpsClient, HttpsClient, LimitError,
+    Method, Proxy, ProxyError, Request, Response, DEFAULT_MAX_BODY_SIZE, DEFAULT_MAX_REDIRECTS,
+    DEFAULT_USER_AGENT,
+};
+
+/// Settings for `ReqwestImpl` and `AsyncReqwestImpl`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ReqwestConfig {
+    /// Timeout for establishing connection.
+    pub connect_timeout: Duration,
+    /// Timeout of whole request, including reading of body (`reqwest` has no
+    /// separate read timeout).
+    pub timeout: Duration,
+    /// Maximal size of response body in bytes, `None` means unlimited.
+    pub max_body_size: Option<u64>,
+    /// Maximal number of followed redirects.
+    pub max_redirects: u32,
+    /// Value of `User-Agent` header.
+    pub user_agent: String,
+}
+
+impl Default for ReqwestConfig {
+    fn default() -> Self {
+        Self {
+            connect_timeout: Duration::from_secs(10),
+            timeout: Duration::from_secs(30),
+            max_body_size: Some(DEFAULT_MAX_BODY_SIZE),
+            max_redirects: DEFAULT_MAX_REDIRECTS,
+            user_agent: DEFAULT_USER_AGENT.into(),
+        }
+    }
+}
+
+/// Number of redirects, followed by `reqwest` by default.
+const REQWEST_MAX_REDIRECTS: u32 = 10;
+
+/// Convert `reqwest` error, so it can be classified by wrappers.
+fn map_err(err: reqwest::Error, max_redirects: u32) -> Box<dyn Error + Send + Sync> {
+    if err.is_redirect() {
+        return Box::new(LimitError::TooManyRedirects {
+            limit: max_redirects,
+        });
+    }
+    let kind = if err.is_timeout() {
+        std::io::ErrorKind::TimedOut
+    } else if err.is_connect() {
+        std::io::ErrorKind::ConnectionRefused
+    } else {
+        return Box::new(err);
+    };
+    Box::new(std::io::Error::new(kind, err))
+}
+
+fn convert_method(method: Method) -> reqwest::Method {
+    match method {
+        Method::Get => reqwest::Method::GET,
+        Method::Head => reqwest::Method::HEAD,
+    }
+}
+
+/// Convert proxy settings. All schemes are supported.
+fn convert_proxy(proxy: &Proxy) -> Result<reqwest::Proxy, ProxyError> {
+    reqwest::Proxy::all(proxy.url()).map_err(|err| ProxyError::Client(err.to_string()))
+}
+
+fn convert_headers(headers: &HeaderMap) -> Vec<(String, String)> {
+    headers
+        .iter()
+        .filter_map(|(name, value)| Some((name.to_string(), value.to_str().ok()?.to_string())))
+        .collect()
+}
+
+/// `HttpsClient` implementation for blocking `reqwest` client.
+///
+/// Connections are pooled and reused by underlying client. It must not
+/// be used inside async runtime, use `AsyncReqwestImpl` there.
+#[derive(Clone)]
+pub struct ReqwestImpl {
+    client: reqwest::blocking::Client,
+    max_body_size: Option<u64>,
+    max_redirects: u32,
+}
+
+impl ReqwestImpl {
+    /// Build client with default settings.
+    ///
+    /// # Panics
+    ///
+    /// Panics if TLS backend cannot be initialized, like `reqwest` clients.
+    pub fn new() -> Self {
+        Self::with_config(ReqwestConfig::default()).expect("Failed to build reqwest client")
+    }
+
+    /// Build client with provided settings. Fails if settings are invalid
+    /// (like `User-Agent`) or TLS backend cannot be initialized.
+    pub fn with_config(config: ReqwestConfig) -> Result<Self, reqwest::Error> {
+        let client = Self::builder(&config).build()?;
+        Ok(Self::from_parts(client, &config))
+    }
+
+    /// Build client from configured `reqwest` client. Body size is limited
+    /// by `DEFAULT_MAX_BODY_SIZE`.
+    pub fn with_client(client: reqwest::blocking::Client) -> Self {
+        Self {
+            client,
+            max_body_size: Some(DEFAULT_MAX_BODY_SIZE),
+            max_redirects: REQWEST_MAX_REDIRECTS,
+        }
+    }
+
+    /// Build client with provided settings, which sends all requests through
+    /// proxy.
+    pub fn with_proxy(config: ReqwestConfig, proxy: Proxy) -> Result<Self, ProxyError> {
+        let client = Self::builder(&config)
+            .proxy(convert_proxy(&proxy)?)
+            .build()
+            .map_err(|err| ProxyError::Client(err.to_string()))?;
+        Ok(Self::from_parts(client, &config))
+    }
+
+    fn builder(config: &ReqwestConfig) -> reqwest::blocking::ClientBuilder {
+        reqwest::blocking::Client::builder()
+            .user_agent(config.user_agent.as_str())
+            .connect_timeout(config.connect_timeout)
+            .timeout(config.timeout)
+            .redirect(reqwest::redirect::Policy::limited(
+                config.max_redirects as usize,
+            ))
+    }
+
+    fn from_parts(client: reqwest::blocking::Client, config: &ReqwestConfig) -> Self {
+        Self {
+            client,
+            max_body_size: config.max_body_size,
+            max_redirects: config.max_redirects,
+        }
+    }
+}
+
+impl Default for ReqwestImpl {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl HttpsClient for ReqwestImpl {
+    fn get(&self, url: &str) -> Result<String, Box<dyn Error>> {
+        get_via_execute(self, url)
+    }
+
+    fn execute(&self, request: &Request) -> Result<Response, Box<dyn Error>> {
+        let mut builder = self
+            .client
+            .request(convert_method(request.method), &request.url);
+        for (name, value) in &request.headers {
+            builder = builder.header(name.as_str(), value.as_str());
+        }
+        if let Some(cookie) = request.cookies.to_header() {
+            builder = builder.header(COOKIE, cookie);
+        }
+
+        let response = builder
+            .send()
+            .map_err(|err| map_err(err, self.max_redirects) as Box<dyn Error>)?;
+        check_content_length(response.content_length(), self.max_body_size)?;
+        Ok(Response {
+            url: request.url.clone(),
+            status: response.status().as_u16(),
+            headers: convert_headers(response.headers()),
+            body: read_body(response, self.max_body_size)?,
+        })
+    }
+}
+
+/// `AsyncHttpsClient` implementation for async `reqwest` client.
+///
+/// Connections are pooled and reused by underlying client.
+#[derive(Clone)]
+pub struct AsyncReqwestImpl {
+    client: reqwest::Client,
+    max_body_size: Option<u64>,
+    max_redirects: u32,
+}
+
+impl AsyncReqwestImpl {
+    /// Build client with default settings.
+    ///
+    /// # Panics
+    ///
+    /// Panics if TLS backend cannot be initialized, like `reqwest` clients.
+    pub fn new() -> Self {
+        Self::with_config(ReqwestConfig::default()).expect("Failed to build reqwest client")
+    }
+
+    /// Build client with provided settings. Fails if settings are invalid
+    /// (like `User-Agent`) or TLS backend cannot be initialized.
+    pub fn with_config(config: ReqwestConfig) -> Result<Self, reqwest::Error> {
+        let client = Self::builder(&config).build()?;
+        Ok(Self::from_parts(client, &config))
+    }
+
+    /// Build client from configured `reqwest` client. Body size is limited
+    /// by `DEFAULT_MAX_BODY_SIZE`.
+    pub fn with_client(client: reqwest::Client) -> Self {
+        Self {
+            client,
+            max_body_size: Some(DEFAULT_MAX_BODY_SIZE),
+            max_redirects: REQWEST_MAX_REDIRECTS,
+        }
+    }
+
+    /// Build client with provided settings, which sends all requests through
+    /// proxy.
+    pub fn with_proxy(config: ReqwestConfig, proxy: Proxy) -> Result<Self, ProxyError> {
+        let client = Self::builder(&config)
+            .proxy(convert_proxy(&proxy)?)
+            .build()
+            .map_err(|err| ProxyError::Client(err.to_string()))?;
+        Ok(Self::from_parts(client, &config))
+    }
+
+    fn builder(config: &ReqwestConfig) -> reqwest::ClientBuilder {
+        reqwest::Client::builder()
+            .user_agent(config.user_agent.as_str())
+            .connect_timeout(config.connect_timeout)
+            .timeout(config.timeout)
+            .redirect(reqwest::redirect::Policy::limited(
+                config.max_redirects as usize,
+            ))
+    }
+
+    fn from_parts(client: reqwest::Client, config: &ReqwestConfig) -> Self {
+        Self {
+            client,
+            max_body_size: config.max_body_size,
+            max_redirects: config.max_redirects,
+        }
+    }
+}
+
+impl Default for AsyncReqwestImpl {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait::async_trait]
+impl AsyncHttpsClient for AsyncReqwestImpl {
+    async fn get(&self, url: &str) -> Result<String, Box<dyn Error + Send + Sync>> {
+        Ok(self.execute(&Request::get(url)).await?.into_body()?)
+    }
+
+    async fn execute(&self, request: &Request) -> Result<Response, Box<dyn Error + Send + Sync>> {
+        let mut builder = self
+            .client
+            .request(convert_method(request.method), &request.url);
+        for (name, value) in &request.headers {
+            builder = builder.header(name.as_str(), value.as_str());
+        }
+        if let Some(cookie) = request.cookies.to_header() {
+            builder = builder.header(COOKIE, cookie);
+        }
+
+        let mut response = builder
+            .send()
+            .await
+            .map_err(|err| map_err(err, self.max_redirects))?;
+        check_content_length(response.content_length(), self.max_body_size)?;
+        let status = response.status().as_u16();
+        let headers = convert_headers(response.headers());
+
+        let mut body = Vec::new();
+        while let Some(chunk) = response
+            .chunk()
+            .await
+            .map_err(|err| map_err(err, self.max_redirects))?
+        {
+            body.extend_from_slice(&chunk);
+            if let Some(limit) = self
+                .max_body_size
+                .filter(|limit| body.len() as u64 > *limit)
+            {
+                return Err(Box::new(LimitError::BodyTooLarge { limit }));
+            }
+        }
+        Ok(Response {
+            url: request.url.clone(),
+            status,
+            headers,
+            body: String::from_utf8_lossy(&body).into_owned(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::*;
+    use crate::https_client::{is_transient_error, test_server, HttpStatusError};
+
+    #[test]
+    fn get() {
+        assert!(ReqwestImpl::new().get(&test_server::serve()).is_ok())
+    }
+
+    #[test]
+    fn get_err() {
+        let err = ReqwestImpl::new()
+            .get(&format!("{}/unknown", test_server::serve()))
+            .unwrap_err();
+        assert_eq!(err.downcast_ref::<HttpStatusError>().unwrap().status, 404);
+    }
+
+    #[test]
+    fn get_retry_after() {
+        let err = ReqwestImpl::new()
+            .get(&format!("{}/busy", test_server::serve()))
+            .unwrap_err();
+        assert_eq!(
+            err.downcast_ref::<HttpStatusError>().unwrap().retry_after,
+            Some(Duration::from_secs(7))
+        );
+    }
+
+    #[test]
+    fn get_connection_refused() {
+        let err = ReqwestImpl::new().get("http://127.0.0.1:1").unwrap_err();
+        assert!(is_transient_error(err.as_ref()));
+    }
+
+    #[test]
+    fn default_user_agent() {
+        let url = format!("{}/user-agent", test_server::serve());
+        assert_eq!(ReqwestImpl::new().get(&url).unwrap(), DEFAULT_USER_AGENT);
+    }
+
+    #[test]
+    fn execute_headers_and_cookies() {
+        let url = test_server::serve();
+        let client = ReqwestImpl::new();
+
+        let request = Request::get(format!("{}/user-agent", url)).with_header("User-Agent", "test");
+        assert_eq!(client.execute(&request).unwrap().body, "test");
+
+        let request = Request::get(format!("{}/cookie", url)).with_cookie("timezone", "UTC");
+        assert_eq!(client.execute(&request).unwrap().body, "timezone=UTC");
+    }
+
+    #[test]
+    fn limits() {
+        let url = test_server::serve();
+        let client = ReqwestImpl::with_config(ReqwestConfig {
+            timeout: Duration::from_millis(200),
+            max_body_size: Some(1024),
+            max_redirects: 2,
+            ..ReqwestConfig::default()
+        })
+        .unwrap();
+
+        let err = client.get(&format!("{}/slow", url)).unwrap_err();
+        assert_eq!(
+            crate::https_client::find_io_error_kind(err.as_ref()),
+            Some(std::io::ErrorKind::TimedOut)
+        );
+        let err = client.get(&format!("{}/large", url)).unwrap_err();
+        assert_eq!(
+            err.downcast_ref::<LimitError>(),
+            Some(&LimitError::BodyTooLarge { limit: 1024 })
+        );
+        let err = client.get(&format!("{}/redirect", url)).unwrap_err();
+        assert_eq!(
+            err.downcast_ref::<LimitError>(),
+            Some(&LimitError::TooManyRedirects { limit: 2 })
+        );
+        assert!(client.get(&url).is_ok());
+    }
+
+    #[test]
+    fn invalid_config() {
+        let config = ReqwestConfig {
+            user_agent: "invalid\n".into(),
+            ..ReqwestConfig::default()
+        };
+        assert!(ReqwestImpl::with_config(config.clone()).is_err());
+        assert!(AsyncReqwestImpl::with_config(config).is_err());
+    }
+
+    #[tokio::test]
+    async fn async_limits() {
+        let url = test_server::serve();
+        let client = AsyncReqwestImpl::with_config(ReqwestConfig {
+            max_body_size: Some(1024),
+            ..ReqwestConfig::default()
+        })
+        .unwrap();
+        let err = client.get(&format!("{}/large", url)).await.unwrap_err();
+        assert_eq!(
+            err.downcast_ref::<LimitError>(),
+            Some(&LimitError::BodyTooLarge { limit: 1024 })
+        );
+    }
+
+    #[test]
+    fn proxy() {
+        let client = ReqwestImpl::with_proxy(
+            ReqwestConfig::default(),
+            test_server::serve().parse().unwrap(),
+        )
+        .unwrap();
+        assert_eq!(client.get("http://hltv.invalid/").unwrap(), "proxied");
+
+        // Settings are applied to proxied client
+        let config = ReqwestConfig {
+            max_body_size: Some(3),
+            ..ReqwestConfig::default()
+        };
+        let client = ReqwestImpl::with_proxy(config, test_server::serve().parse().unwrap());
+        let err = client.unwrap().get("http://hltv.invalid/").unwrap_err();
+        assert_eq!(
+            err.downcast_ref::<LimitError>(),
+            Some(&LimitError::BodyTooLarge { limit: 3 })
+        );
+    }
+
+    #[tokio::test]
+    async fn async_get() {
+        let url = test_server::serve();
+        let client = AsyncReqwestImpl::new();
+        assert!(client.get(&url).await.is_ok());
+        let err = client.get(&format!("{}/unknown", url)).await.unwrap_err();
+        assert_eq!(err.downcast_ref::<HttpStatusError>().unwrap().status, 404);
+
+        let request = Request::get(format!("{}/cookie", url)).with_cookie("timezone", "UTC");
+        assert_eq!(client.execute(&request).await.unwrap().body, "timezone=UTC");
+    }
+
+    #[test]
+    #[ignore = "requires network access"]
+    fn get_ssl() {
+        assert!(ReqwestImpl::new().get("https://example.com").is_ok())
+    }
+}
@@ -0,0 +1,257 @@
+use std::{error::Error, time::Duration};
+
+use crate::https_client::{
+    check_content_length, get_via_execute, read_body, HttpsClient, LimitError, Proxy, ProxyError,
+    ProxyScheme, Request, Response, DEFAULT_MAX_BODY_SIZE, DEFAULT_MAX_REDIRECTS,
+    DEFAULT_USER_AGENT,
+};
+
+/// Settings for `UreqImpl`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct UreqConfig {
+    /// Timeout for establishing connection.
+    pub connect_timeout: Duration,
+    /// Timeout for reading from socket.
+    pub read_timeout: Duration,
+    /// Number of idle connections, kept alive for reuse (per host).
+    pub max_idle_connections_per_host: usize,
+    /// Value of `User-Agent` header.
+    pub user_agent: String,
+    /// Maximal size of response body in bytes, `None` means unlimited.
+    pub max_body_size: Option<u64>,
+    /// Maximal number of followed redirects.
+    pub max_redirects: u32,
+}
+
+impl Default for UreqConfig {
+    fn default() -> Self {
+        Self {
+            connect_timeout: Duration::from_secs(10),
+            read_timeout: Duration::from_secs(30),
+            max_idle_connections_per_host: 2,
+            user_agent: DEFAULT_USER_AGENT.into(),
+            max_body_size: Some(DEFAULT_MAX_BODY_SIZE),
+            max_redirects: DEFAULT_MAX_REDIRECTS,
+        }
+    }
+}
+
+/// `HttpsClient` implementation for `ureq` crate.
+///
+/// Uses single agent, so connections are kept alive between requests.
+#[derive(Clone)]
+pub struct UreqImpl {
+    agent: ureq::Agent,
+    max_body_size: Option<u64>,
+    max_redirects: u32,
+}
+
+impl UreqImpl {
+    /// Build client with default settings.
+    pub fn new() -> Self {
+        Self::with_config(UreqConfig::default())
+    }
+
+    /// Build client with provided settings.
+    pub fn with_config(config: UreqConfig) -> Self {
+        Self {
+            agent: agent_builder(&config).build(),
+            max_body_size: config.max_body_size,
+            max_redirects: config.max_redirects,
+        }
+    }
+
+    /// Build client with provided settings, which sends all requests through
+    /// proxy. Only HTTP and SOCKS5 proxies are supported.
+    pub fn with_proxy(config: UreqConfig, proxy: Proxy) -> Result<Self, ProxyError> {
+        proxy.check_scheme(&[ProxyScheme::Http, ProxyScheme::Socks5], "ureq")?;
+        let proxy = ureq::Proxy::new(proxy.url())
+            .map_err(|err| ProxyError::InvalidUrl(format!("{} ({})", proxy, err)))?;
+        Ok(Self {
+            agent: agent_builder(&config).proxy(proxy).build(),
+            max_body_size: config.max_body_size,
+            max_redirects: config.max_redirects,
+        })
+    }
+}
+
+fn agent_builder(config: &UreqConfig) -> ureq::AgentBuilder {
+    ureq::AgentBuilder::new()
+        .timeout_connect(config.connect_timeout)
+        .timeout_read(config.read_timeout)
+        .max_idle_connections_per_host(config.max_idle_connections_per_host)
+        .user_agent(&config.user_agent)
+        .redirects(config.max_redirects)
+}
+
+impl Default for UreqImpl {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+fn convert_response(
+    url: &str,
+    response: ureq::Response,
+    max_body_size: Option<u64>,
+) -> Result<Response, Box<dyn Error>> {
+    let status = response.status();
+    let headers = response
+        .headers_names()
+        .into_iter()
+        .filter_map(|name| {
+            let value = response.header(&name)?.to_string();
+            Some((name, value))
+        })
+        .collect();
+    let content_length = response
+        .header("Content-Length")
+        .and_then(|value| value.parse().ok());
+    check_content_length(content_length, max_body_size)?;
+    Ok(Response {
+        url: url.into(),
+        status,
+        headers,
+        body: read_body(response.into_reader(), max_body_size)?,
+    })
+}
+
+impl HttpsClient for UreqImpl {
+    fn get(&self, url: &str) -> Result<String, Box<dyn Error>> {
+        get_via_execute(self, url)
+    }
+
+    fn execute(&self, request: &Request) -> Result<Response, Box<dyn Error>> {
+        let mut ureq_request = self.agent.request(request.method.as_str(), &request.url);
+        for (name, value) in &request.headers {
+            ureq_request = ureq_request.set(name, value);
+        }
+        if let Some(cookie) = request.cookies.to_header() {
+            ureq_request = ureq_request.set("Cookie", &cookie);
+        }
+
+        match ureq_request.call() {
+            Ok(response) | Err(ureq::Error::Status(_, response)) => {
+                convert_response(&request.url, response, self.max_body_size)
+            }
+            Err(err) if err.kind() == ureq::ErrorKind::TooManyRedirects => {
+                Err(Box::new(LimitError::TooManyRedirects {
+                    limit: self.max_redirects,
+                }))
+            }
+            // Transport error keeps underlying IO error as source
+            Err(err) => Err(Box::new(err)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::https_client::{is_transient_error, test_server, HttpStatusError};
+
+    #[test]
+    fn get() {
+        assert!(UreqImpl::new().get(&test_server::serve()).is_ok())
+    }
+
+    #[test]
+    fn get_err() {
+        let err = UreqImpl::new()
+            .get(&format!("{}/unknown", test_server::serve()))
+            .unwrap_err();
+        assert_eq!(err.downcast_ref::<HttpStatusError>().unwrap().status, 404);
+    }
+
+    #[test]
+    fn get_retry_after() {
+        let err = UreqImpl::new()
+            .get(&format!("{}/busy", test_server::serve()))
+            .unwrap_err();
+        assert_eq!(
+            err.downcast_ref::<HttpStatusError>().unwrap().retry_after,
+            Some(Duration::from_secs(7))
+        );
+    }
+
+    #[test]
+    fn get_connection_refused() {
+        let err = UreqImpl::new().get("http://127.0.0.1:1").unwrap_err();
+        assert!(is_transient_error(err.as_ref()));
+    }
+
+    #[test]
+    fn user_agent() {
+        let client = UreqImpl::with_config(UreqConfig {
+            user_agent: "test-agent".into(),
+            ..UreqConfig::default()
+        });
+        let url = format!("{}/user-agent", test_server::serve());
+        assert_eq!(client.get(&url).unwrap(), "test-agent");
+    }
+
+    #[test]
+    fn execute_headers_and_cookies() {
+        let url = test_server::serve();
+        let client = UreqImpl::new();
+
+        let request = Request::get(format!("{}/user-agent", url)).with_header("User-Agent", "test");
+        assert_eq!(client.execute(&request).unwrap().body, "test");
+
+        let request = Request::get(format!("{}/cookie", url)).with_cookie("timezone", "UTC");
+        assert_eq!(client.execute(&request).unwrap().body, "timezone=UTC");
+    }
+
+    #[test]
+    fn limits() {
+        let url = test_server::serve();
+        let client = UreqImpl::with_config(UreqConfig {
+            read_timeout: Duration::from_millis(200),
+            max_body_size: Some(1024),
+            max_redirects: 2,
+            ..UreqConfig::default()
+        });
+
+        let err = client.get(&format!("{}/slow", url)).unwrap_err();
+        assert_eq!(
+            crate::https_client::find_io_error_kind(err.as_ref()),
+            Some(std::io::ErrorKind::TimedOut)
+        );
+        let err = client.get(&format!("{}/large", url)).unwrap_err();
+        assert_eq!(
+            err.downcast_ref::<LimitError>(),
+            Some(&LimitError::BodyTooLarge { limit: 1024 })
+        );
+        let err = client.get(&format!("{}/redirect", url)).unwrap_err();
+        assert_eq!(
+            err.downcast_ref::<LimitError>(),
+            Some(&LimitError::TooManyRedirects { limit: 2 })
+        );
+        assert!(client.get(&url).is_ok());
+    }
+
+    #[test]
+    fn proxy() {
+        let proxy = test_server::serve().parse().unwrap();
+        let client = UreqImpl::with_proxy(UreqConfig::default(), proxy).unwrap();
+        assert_eq!(client.get("http://hltv.invalid/").unwrap(), "proxied");
+
+        let proxy = Proxy::new(ProxyScheme::Https, "127.0.0.1", 443);
+        assert!(matches!(
+            UreqImpl::with_proxy(UreqConfig::default(), proxy),
+            Err(ProxyError::Unsupported { .. })
+        ));
+    }
+
+    #[test]
+    #[ignore = "requires network access"]
+    fn get_ssl() {
+        assert!(UreqImpl::new().get("https://example.com").is_ok())
+    }
+
+    #[test]
+    #[ignore = "requires network access"]
+    fn get_ssl_err() {
+        assert!(UreqImpl::new().get("https://example.com/unknown").is_err())
+    }
+}
@@ -0,0 +1,95 @@
+/// Error, returned by provided clients when response exceeds configured
+/// limit.
+#[derive(Debug, Clone, PartialEq, thiserror::Error)]
+pub enum LimitError {
+    #[error("Response body is larger than {limit} bytes")]
+    BodyTooLarge { limit: u64 },
+
+    #[error("Response is redirected more than {limit} times")]
+    TooManyRedirects { limit: u32 },
+}
+
+/// Default limit of response body size (10 MiB).
+pub const DEFAULT_MAX_BODY_SIZE: u64 = 10 * 1024 * 1024;
+
+/// Default limit of followed redirects.
+pub const DEFAULT_MAX_REDIRECTS: u32 = 5;
+
+/// Read response body as UTF-8 (invalid sequences are replaced), failing if
+/// it is larger than `limit`. Socket read timeouts are reported as
+/// `TimedOut`.
+#[cfg(any(
+    feature = "attohttpc_client",
+    feature = "reqwest_client",
+    feature = "ureq_client"
+))]
+pub(crate) fn read_body<R: std::io::Read>(
+    mut reader: R,
+    limit: Option<u64>,
+) -> Result<String, Box<dyn std::error::Error>> {
+    use std::io::{self, Read};
+
+    let mut body = Vec::new();
+    let res = match limit {
+        Some(limit) => reader
+            .by_ref()
+            .take(limit.saturating_add(1))
+            .read_to_end(&mut body),
+        None => reader.read_to_end(&mut body),
+    };
+    match res {
+        Err(err) if err.kind() == io::ErrorKind::WouldBlock => {
+            return Err(Box::new(io::Error::new(io::ErrorKind::TimedOut, err)))
+        }
+        res => res?,
+    };
+    if let Some(limit) = limit {
+        if body.len() as u64 > limit {
+            return Err(Box::new(LimitError::BodyTooLarge { limit }));
+        }
+    }
+    Ok(String::from_utf8_lossy(&body).into_owned())
+}
+
+/// Fail early, if `Content-Length` of response is larger than `limit`.
+#[cfg(any(
+    feature = "attohttpc_client",
+    feature = "reqwest_client",
+    feature = "ureq_client"
+))]
+pub(crate) fn check_content_length(
+    content_length: Option<u64>,
+    limit: Option<u64>,
+) -> Result<(), LimitError> {
+    match (content_length, limit) {
+        (Some(length), Some(limit)) if length > limit => Err(LimitError::BodyTooLarge { limit }),
+        _ => Ok(()),
+    }
+}
+
+#[cfg(all(
+    test,
+    any(
+        feature = "attohttpc_client",
+        feature = "reqwest_client",
+        feature = "ureq_client"
+    )
+))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn body_limit() {
+        assert_eq!(read_body(&b"12345"[..], Some(5)).unwrap(), "12345");
+        assert_eq!(read_body(&b"12345"[..], None).unwrap(), "12345");
+        let err = read_body(&b"123456"[..], Some(5)).unwrap_err();
+        assert_eq!(
+            err.downcast_ref::<LimitError>(),
+            Some(&LimitError::BodyTooLarge { limit: 5 })
+        );
+
+        assert!(check_content_length(Some(6), Some(5)).is_err());
+        assert!(check_content_length(Some(5), Some(5)).is_ok());
+        assert!(check_content_length(None, Some(5)).is_ok());
+    }
+}
@@ -0,0 +1,390 @@
+use std::{
+    error::Error,
+    future::Future,
+    io,
+    pin::Pin,
+    sync::{Arc, Mutex, MutexGuard},
+    task::{Context, Poll, Waker},
+    thread,
+    time::Duration,
+};
+
+use super::{get_via_execute, url_path, AsyncHttpsClient, HttpsClient, Request, Response};
+
+/// Canned result of `MockClient` route.
+#[derive(Clone, Debug, PartialEq)]
+pub enum MockResponse {
+    /// Response with status, headers and body.
+    Response {
+        status: u16,
+        headers: Vec<(String, String)>,
+        body: String,
+    },
+    /// Transport error (`std::io::Error` of provided kind).
+    Error(io::ErrorKind),
+}
+
+impl MockResponse {
+    /// Successful response with provided body.
+    pub fn ok<B: Into<String>>(body: B) -> Self {
+        Self::status(200, body)
+    }
+
+    /// Response with provided status and body.
+    pub fn status<B: Into<String>>(status: u16, body: B) -> Self {
+        Self::Response {
+            status,
+            headers: Vec::new(),
+            body: body.into(),
+        }
+    }
+
+    /// Add header to response. Does nothing for errors.
+    pub fn with_header<N: Into<String>, V: Into<String>>(mut self, name: N, value: V) -> Self {
+        if let Self::Response { headers, .. } = &mut self {
+            headers.push((name.into(), value.into()));
+        }
+        self
+    }
+
+    fn to_result(&self, url: &str) -> io::Result<Response> {
+        match self {
+            Self::Response {
+                status,
+                headers,
+                body,
+            } => Ok(Response {
+                url: url.into(),
+                status: *status,
+                headers: headers.clone(),
+                body: body.clone(),
+            }),
+            Self::Error(kind) => Err(io::Error::new(*kind, format!("Mock error for {}", url))),
+        }
+    }
+}
+
+#[derive(Default)]
+struct MockState {
+    requests: Vec<Request>,
+    fail_next: u32,
+}
+
+/// Fake `HttpsClient` and `AsyncHttpsClient` for tests, which serves canned
+/// responses without network.
+///
+/// Request is answered by first route, which pattern matches its URL (see
+/// `MockClient::with_route`), or with 404. Every request is recorded.
+/// Latency and failures can be injected to test retries and timeouts.
+#[derive(Default)]
+pub struct MockClient {
+    routes: Vec<(String, MockResponse)>,
+    latency: Duration,
+    failure_rate: f64,
+    failure: Option<io::ErrorKind>,
+    state: Mutex<MockState>,
+}
+
+impl MockClient {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Answer requests, which URL matches `pattern`, with `response`.
+    ///
+    /// Pattern may contain `*`, which matches any sequence of characters.
+    /// Pattern, which starts with `/`, is matched against path and query of
+    /// URL, otherwise against whole URL.
+    pub fn with_route<P: Into<String>>(mut self, pattern: P, response: MockResponse) -> Self {
+        self.routes.push((pattern.into(), response));
+        self
+    }
+
+    /// Delay every response. Async requests wait without blocking executor
+    /// thread.
+    pub fn with_latency(mut self, latency: Duration) -> Self {
+        self.latency = latency;
+        self
+    }
+
+    /// Fail random requests with given probability (from 0 to 1) with
+    /// `ConnectionReset` error.
+    pub fn with_failure_rate(mut self, failure_rate: f64) -> Self {
+        self.failure_rate = failure_rate;
+        self
+    }
+
+    /// Set kind of injected errors (`ConnectionReset` by default).
+    pub fn with_failure_kind(mut self, kind: io::ErrorKind) -> Self {
+        self.failure = Some(kind);
+        self
+    }
+
+    /// Fail next `count` requests with injected error.
+    pub fn fail_next(&self, count: u32) {
+        self.lock().fail_next = count;
+    }
+
+    /// Get all received requests.
+    pub fn requests(&self) -> Vec<Request> {
+        self.lock().requests.clone()
+    }
+
+    /// Get URLs of all received requests.
+    pub fn requested_urls(&self) -> Vec<String> {
+        self.lock()
+            .requests
+            .iter()
+            .map(|request| request.url.clone())
+            .collect()
+    }
+
+    /// Forget received requests.
+    pub fn clear_requests(&self) {
+        self.lock().requests.clear();
+    }
+
+    fn lock(&self) -> MutexGuard<'_, MockState> {
+        self.state.lock().unwrap_or_else(|err| err.into_inner())
+    }
+
+    /// Record request and decide, whether it must fail.
+    fn receive(&self, request: &Request) -> bool {
+        let mut state = self.lock();
+        state.requests.push(request.clone());
+        if state.fail_next > 0 {
+            state.fail_next -= 1;
+            true
+        } else {
+            self.failure_rate > 0.0 && rand::random::<f64>() < self.failure_rate
+        }
+    }
+
+    fn respond(&self, request: &Request, inject_failure: bool) -> io::Result<Response> {
+        if inject_failure {
+            let kind = self.failure.unwrap_or(io::ErrorKind::ConnectionReset);
+            return Err(io::Error::new(kind, "Injected failure"));
+        }
+
+        let path = url_path(&request.url);
+        match self.routes.iter().find(|(pattern, _)| {
+            if pattern.starts_with('/') {
+                matches_pattern(pattern, path)
+            } else {
+                matches_pattern(pattern, &request.url)
+            }
+        }) {
+            Some((_, response)) => response.to_result(&request.url),
+            None => MockResponse::status(404, "Not found").to_result(&request.url),
+        }
+    }
+}
+
+/// Match text against pattern, where `*` matches any sequence of characters.
+fn matches_pattern(pattern: &str, text: &str) -> bool {
+    let mut parts = pattern.split('*');
+    let first = parts.next().unwrap_or_default();
+    let mut rest = match text.strip_prefix(first) {
+        Some(rest) => rest,
+        None => return false,
+    };
+    let parts = parts.collect::<Vec<_>>();
+    let (last, middle) = match parts.split_last() {
+        Some(parts) => parts,
+        // No wildcards
+        None => return rest.is_empty(),
+    };
+    for part in middle {
+        match rest.find(part) {
+            Some(idx) => rest = &rest[idx + part.len()..],
+            None => return false,
+        }
+    }
+    rest.ends_with(last)
+}
+
+impl HttpsClient for MockClient {
+    fn get(&self, url: &str) -> Result<String, Box<dyn Error>> {
+        get_via_execute(self, url)
+    }
+
+    fn execute(&self, request: &Request) -> Result<Response, Box<dyn Error>> {
+        let inject_failure = self.receive(request);
+        if self.latency > Duration::from_secs(0) {
+            thread::sleep(self.latency);
+        }
+        Ok(self.respond(request, inject_failure)?)
+    }
+}
+
+#[async_trait::async_trait]
+impl AsyncHttpsClient for MockClient {
+    async fn get(&self, url: &str) -> Result<String, Box<dyn Error + Send + Sync>> {
+        Ok(AsyncHttpsClient::execute(self, &Request::get(url))
+            .await?
+            .into_body()?)
+    }
+
+    async fn execute(&self, request: &Request) -> Result<Response, Box<dyn Error + Send + Sync>> {
+        let inject_failure = self.receive(request);
+        if self.latency > Duration::from_secs(0) {
+            Delay::new(self.latency).await;
+        }
+        Ok(self.respond(request, inject_failure)?)
+    }
+}
+
+#[derive(Default)]
+struct DelayState {
+    elapsed: bool,
+    waker: Option<Waker>,
+}
+
+/// Future, which completes after provided time. Time is measured by
+/// separate thread, since crate does not depend on async runtime.
+struct Delay(Arc<Mutex<DelayState>>);
+
+impl Delay {
+    fn new(duration: Duration) -> Self {
+        let state = Arc::new(Mutex::new(DelayState::default()));
+        let timer = state.clone();
+        thread::spawn(move || {
+            thread::sleep(duration);
+            let mut state = timer.lock().unwrap_or_else(|err| err.into_inner());
+            state.elapsed = true;
+            if let Some(waker) = state.waker.take() {
+                waker.wake();
+            }
+        });
+        Self(state)
+    }
+}
+
+impl Future for Delay {
+    type Output = ();
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<()> {
+        let mut state = self.0.lock().unwrap_or_else(|err| err.into_inner());
+        if state.elapsed {
+            Poll::Ready(())
+        } else {
+            state.waker = Some(cx.waker().clone());
+            Poll::Pending
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    // Not glob import, since `AsyncHttpsClient` methods have the same names
+    use super::{io, matches_pattern, Duration, HttpsClient, MockClient, MockResponse, Request};
+    use crate::HttpStatusError;
+
+    #[test]
+    fn patterns() {
+        assert!(matches_pattern("/matches", "/matches"));
+        assert!(!matches_pattern("/matches", "/matches/1"));
+        assert!(matches_pattern("/results*", "/results?offset=100"));
+        assert!(matches_pattern("/news/*/*", "/news/archive/2019/may"));
+        assert!(matches_pattern("*/may", "/news/archive/2019/may"));
+        assert!(!matches_pattern("/news/*/june", "/news/archive/2019/may"));
+        assert!(matches_pattern("*", ""));
+    }
+
+    #[test]
+    fn routes() {
+        let client = MockClient::new()
+            .with_route("/matches", MockResponse::ok("matches"))
+            .with_route(
+                "https://www.hltv.org/results*",
+                MockResponse::status(503, "busy").with_header("Retry-After", "7"),
+            )
+            .with_route("/news/*", MockResponse::Error(io::ErrorKind::TimedOut));
+
+        assert_eq!(
+            client.get("https://www.hltv.org/matches").unwrap(),
+            "matches"
+        );
+        let err = client
+            .get("https://www.hltv.org/results?offset=0")
+            .unwrap_err();
+        let err = err.downcast_ref::<HttpStatusError>().unwrap();
+        assert_eq!(err.status, 503);
+        assert_eq!(err.retry_after, Some(Duration::from_secs(7)));
+        let err = client.get("https://www.hltv.org/news/archive").unwrap_err();
+        assert_eq!(
+            err.downcast_ref::<io::Error>().unwrap().kind(),
+            io::ErrorKind::TimedOut
+        );
+        let response = client
+            .execute(&Request::get("https://www.hltv.org/unknown"))
+            .unwrap();
+        assert_eq!(response.status, 404);
+
+        assert_eq!(
+            client.requested_urls(),
+            vec![
+                "https://www.hltv.org/matches",
+                "https://www.hltv.org/results?offset=0",
+                "https://www.hltv.org/news/archive",
+                "https://www.hltv.org/unknown",
+            ]
+        );
+        client.clear_requests();
+        assert!(client.requests().is_empty());
+    }
+
+    #[test]
+    fn failure_injection() {
+        let client = MockClient::new()
+            .with_route("*", MockResponse::ok("ok"))
+            .with_failure_kind(io::ErrorKind::ConnectionRefused);
+        client.fail_next(2);
+        assert!(client.get("/").is_err());
+        assert!(client.get("/").is_err());
+        assert!(client.get("/").is_ok());
+
+        let client = MockClient::new().with_failure_rate(1.0);
+        let err = client.get("/").unwrap_err();
+        assert_eq!(
+            err.downcast_ref::<io::Error>().unwrap().kind(),
+            io::ErrorKind::ConnectionReset
+        );
+    }
+
+    #[test]
+    fn latency() {
+        let client = MockClient::new().with_latency(Duration::from_millis(50));
+        let start = std::time::Instant::now();
+        let _ = client.get("/");
+        assert!(start.elapsed() >= Duration::from_millis(50));
+    }
+
+    #[tokio::test]
+    async fn async_latency() {
+        use crate::AsyncHttpsClient;
+
+        let client = MockClient::new()
+            .with_route("*", MockResponse::ok("ok"))
+            .with_latency(Duration::from_millis(50));
+        let start = std::time::Instant::now();
+        // Current thread runtime serves both requests concurrently, since
+        // latency does not block it
+        let (first, second) = tokio::join!(
+            AsyncHttpsClient::get(&client, "/"),
+            AsyncHttpsClient::get(&client, "/")
+        );
+        assert_eq!(first.unwrap(), "ok");
+        assert_eq!(second.unwrap(), "ok");
+        let elapsed = start.elapsed();
+        assert!(elapsed >= Duration::from_millis(50));
+        assert!(elapsed < Duration::from_millis(100));
+    }
+
+    #[test]
+    fn with_api() {
+        let client = MockClient::new().with_route("/", MockResponse::ok("<html></html>"));
+        let api = crate::HltvApi::new(client, "https://www.hltv.org");
+        assert!(api.raw_page("/").is_ok());
+    }
+}
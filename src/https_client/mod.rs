@@ -0,0 +1,308 @@
+use std::{error::Error, time::Duration};
+
+pub use breaker::*;
+pub use cache::*;
+pub use cassette::*;
+pub use cloudflare::*;
+pub use conditional::*;
+pub use limit::*;
+#[cfg(feature = "testing")]
+pub use mock::*;
+pub use proxy::*;
+pub use request::*;
+pub use retry::*;
+pub use throttle::*;
+
+mod breaker;
+mod cache;
+mod cassette;
+mod cloudflare;
+mod conditional;
+mod limit;
+#[cfg(feature = "testing")]
+mod mock;
+mod proxy;
+mod request;
+mod retry;
+mod throttle;
+
+/// HTTPS capable synchronous client.
+///
+/// Simple clients may implement only `get`, in which case `execute` supports
+/// only GET requests and ignores headers and cookies. Full-featured clients
+/// implement `execute` and route `get` through it with `get_via_execute`.
+///
+/// Implementations should return `HttpStatusError` (from `get`) for
+/// non-successful responses and `std::io::Error` for network failures, so
+/// wrappers (like `RetryClient`) can classify them.
+pub trait HttpsClient {
+    /// Perform GET request and return body of successful response.
+    fn get(&self, url: &str) -> Result<String, Box<dyn Error>>;
+
+    /// Perform request. Non-successful responses are returned as `Ok`.
+    fn execute(&self, request: &Request) -> Result<Response, Box<dyn Error>> {
+        check_simple_request(request)?;
+        match self.get(&request.url) {
+            Ok(body) => Ok(Response::ok(request.url.clone(), body)),
+            Err(err) => match err.downcast::<HttpStatusError>() {
+                Ok(err) => Ok(Response::from(*err)),
+                Err(err) => Err(err),
+            },
+        }
+    }
+
+    /// Check whether client sends conditional requests (see
+    /// `ConditionalClient`), so responses may have status `NOT_MODIFIED`.
+    /// Wrappers forward it to wrapped client.
+    fn is_conditional(&self) -> bool {
+        false
+    }
+}
+
+impl<C: HttpsClient + ?Sized> HttpsClient for Box<C> {
+    fn get(&self, url: &str) -> Result<String, Box<dyn Error>> {
+        (**self).get(url)
+    }
+
+    fn execute(&self, request: &Request) -> Result<Response, Box<dyn Error>> {
+        (**self).execute(request)
+    }
+
+    fn is_conditional(&self) -> bool {
+        (**self).is_conditional()
+    }
+}
+
+/// HTTPS capable asynchronous client.
+///
+/// Like `HttpsClient`, simple clients may implement only `get`.
+#[async_trait::async_trait]
+pub trait AsyncHttpsClient: Send + Sync {
+    /// Perform GET request and return body of successful response.
+    async fn get(&self, url: &str) -> Result<String, Box<dyn Error + Send + Sync>>;
+
+    /// Perform request. Non-successful responses are returned as `Ok`.
+    async fn execute(&self, request: &Request) -> Result<Response, Box<dyn Error + Send + Sync>> {
+        check_simple_request(request)?;
+        match self.get(&request.url).await {
+            Ok(body) => Ok(Response::ok(request.url.clone(), body)),
+            Err(err) => match err.downcast::<HttpStatusError>() {
+                Ok(err) => Ok(Response::from(*err)),
+                Err(err) => Err(err),
+            },
+        }
+    }
+}
+
+/// Implementation of `HttpsClient::get` for clients, which implement
+/// `execute`. Request is unconditional (see `Request::unconditional`), since
+/// `get` must return body.
+pub fn get_via_execute<C: HttpsClient + ?Sized>(
+    client: &C,
+    url: &str,
+) -> Result<String, Box<dyn Error>> {
+    Ok(client
+        .execute(&Request::get(url).unconditional())?
+        .into_body()?)
+}
+
+/// Check that request can be performed by client, which implements only `get`.
+fn check_simple_request(request: &Request) -> Result<(), String> {
+    if request.method == Method::Get {
+        Ok(())
+    } else {
+        Err(format!(
+            "{} requests are not supported by this client",
+            request.method.as_str()
+        ))
+    }
+}
+
+/// Non-successful HTTP response status.
+#[derive(Debug, Clone, PartialEq, thiserror::Error)]
+#[error("HTTP status {status} for {url}")]
+pub struct HttpStatusError {
+    pub status: u16,
+    pub url: String,
+    /// Value of `Retry-After` header, if any.
+    pub retry_after: Option<Duration>,
+    /// Response body (empty, if it could not be read).
+    pub body: String,
+}
+
+/// Parse value of `Retry-After` header, which is either number of seconds or
+/// HTTP date.
+pub fn parse_retry_after(value: &str) -> Option<Duration> {
+    let value = value.trim();
+    if let Ok(secs) = value.parse::<u64>() {
+        return Some(Duration::from_secs(secs));
+    }
+    let date = httpdate::parse_http_date(value).ok()?;
+    Some(
+        date.duration_since(std::time::SystemTime::now())
+            .unwrap_or_default(),
+    )
+}
+
+/// FNV-1a hash, which (unlike `DefaultHasher`) is stable between builds.
+fn fnv1a(bytes: &[u8]) -> u64 {
+    bytes.iter().fold(0xcbf2_9ce4_8422_2325, |hash, byte| {
+        (hash ^ u64::from(*byte)).wrapping_mul(0x0100_0000_01b3)
+    })
+}
+
+/// Get path (with query) from URL.
+fn url_path(url: &str) -> &str {
+    let without_scheme = url.find("://").map_or(url, |idx| &url[idx + 3..]);
+    without_scheme
+        .find('/')
+        .map_or("/", |idx| &without_scheme[idx..])
+}
+
+#[test]
+fn url_paths() {
+    assert_eq!(url_path("https://www.hltv.org"), "/");
+    assert_eq!(url_path("https://www.hltv.org/"), "/");
+    assert_eq!(
+        url_path("https://www.hltv.org/results?offset=100"),
+        "/results?offset=100"
+    );
+    assert_eq!(url_path("/matches"), "/matches");
+}
+
+#[test]
+fn parse_retry_after_values() {
+    assert_eq!(parse_retry_after("120"), Some(Duration::from_secs(120)));
+    assert_eq!(
+        parse_retry_after("Wed, 21 Oct 2015 07:28:00 GMT"),
+        Some(Duration::from_secs(0))
+    );
+    assert_eq!(parse_retry_after("soon"), None);
+}
+
+#[test]
+fn assert_https_client_object_safety() {
+    struct NoneHttpsClient;
+
+    impl HttpsClient for NoneHttpsClient {
+        fn get(&self, _url: &str) -> Result<String, Box<dyn Error>> {
+            Ok(String::new())
+        }
+    }
+
+    let _: Box<dyn HttpsClient> = Box::new(NoneHttpsClient);
+}
+
+#[test]
+fn assert_async_https_client_object_safety() {
+    struct NoneAsyncHttpsClient;
+
+    #[async_trait::async_trait]
+    impl AsyncHttpsClient for NoneAsyncHttpsClient {
+        async fn get(&self, _url: &str) -> Result<String, Box<dyn Error + Send + Sync>> {
+            Ok(String::new())
+        }
+    }
+
+    let _: Box<dyn AsyncHttpsClient> = Box::new(NoneAsyncHttpsClient);
+}
+
+/// Minimal local HTTP server for offline client tests.
+#[cfg(all(
+    test,
+    any(
+        feature = "attohttpc_client",
+        feature = "reqwest_client",
+        feature = "ureq_client"
+    )
+))]
+pub(crate) mod test_server {
+    use std::{
+        io::{BufRead, BufReader, Write},
+        net::{TcpListener, TcpStream},
+    };
+
+    /// Start server in background thread and return its base URL.
+    ///
+    /// Server responds to `/` with 200, to `/user-agent` with value of
+    /// `User-Agent` header, to `/cookie` with value of `Cookie` header, to
+    /// `/busy` with 503 and `Retry-After`, to `/slow` after 1 second, to
+    /// `/large` with 64 KiB body, to `/redirect` with endless redirect and to
+    /// everything else with 404.
+    /// Server is also HTTP proxy, which responds to `/` on any host with
+    /// `proxied`.
+    pub fn serve() -> String {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        std::thread::spawn(move || {
+            for stream in listener.incoming().flatten() {
+                std::thread::spawn(move || respond(stream));
+            }
+        });
+        url
+    }
+
+    /// Read request line and `User-Agent` and `Cookie` headers.
+    fn read_head(reader: &mut impl BufRead) -> std::io::Result<(String, String, String)> {
+        let mut request_line = String::new();
+        reader.read_line(&mut request_line)?;
+        let (mut user_agent, mut cookie) = (String::new(), String::new());
+        let mut line = String::new();
+        while reader.read_line(&mut line)? > 2 {
+            if let Some((name, value)) = line.split_once(':') {
+                if name.eq_ignore_ascii_case("user-agent") {
+                    user_agent = value.trim().into();
+                } else if name.eq_ignore_ascii_case("cookie") {
+                    cookie = value.trim().into();
+                }
+            }
+            line.clear();
+        }
+        Ok((request_line, user_agent, cookie))
+    }
+
+    fn respond(mut stream: TcpStream) -> std::io::Result<()> {
+        let mut reader = BufReader::new(stream.try_clone()?);
+        let (mut request_line, mut user_agent, mut cookie) = read_head(&mut reader)?;
+
+        // Act as HTTP proxy: tunnel requests after CONNECT and accept
+        // absolute URLs
+        let mut proxied = false;
+        if request_line.starts_with("CONNECT ") {
+            write!(stream, "HTTP/1.1 200 Connection established\r\n\r\n")?;
+            (request_line, user_agent, cookie) = read_head(&mut reader)?;
+            proxied = true;
+        }
+        let mut path = request_line.split(' ').nth(1).unwrap_or("/");
+        if let Some(url) = path.strip_prefix("http://") {
+            path = url.find('/').map_or("/", |idx| &url[idx..]);
+            proxied = true;
+        }
+
+        if path == "/slow" {
+            std::thread::sleep(std::time::Duration::from_secs(1));
+        }
+        let large = "a".repeat(64 * 1024);
+        let (status, extra_headers, body) = match path {
+            "/" if proxied => ("200 OK", "", "proxied"),
+            "/" => ("200 OK", "", "<html>OK</html>"),
+            "/user-agent" => ("200 OK", "", user_agent.as_str()),
+            "/cookie" => ("200 OK", "", cookie.as_str()),
+            "/busy" => ("503 Service Unavailable", "Retry-After: 7\r\n", "Busy"),
+            "/slow" => ("200 OK", "", "Slow"),
+            "/large" => ("200 OK", "", large.as_str()),
+            "/redirect" => ("302 Found", "Location: /redirect\r\n", ""),
+            _ => ("404 Not Found", "", "Not found"),
+        };
+        write!(
+            stream,
+            "HTTP/1.1 {}\r\n{}Content-Length: {}\r\nConnection: close\r\n\r\n{}",
+            status,
+            extra_headers,
+            body.len(),
+            body
+        )
+    }
+}
+
+pub mod impls;
@@ -0,0 +1,249 @@
+use std::{collections::BTreeMap, time::Duration};
+
+use super::{parse_retry_after, HttpStatusError};
+
+/// Default value of `User-Agent` header for provided clients.
+pub const DEFAULT_USER_AGENT: &str = concat!(
+    "Mozilla/5.0 (compatible; hltv-rs/",
+    env!("CARGO_PKG_VERSION"),
+    ")"
+);
+
+/// HTTP method.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum Method {
+    Get,
+    Head,
+}
+
+impl Method {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Get => "GET",
+            Self::Head => "HEAD",
+        }
+    }
+}
+
+/// Cookies, sent with request.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct CookieJar {
+    cookies: BTreeMap<String, String>,
+}
+
+impl CookieJar {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add cookie, replacing previous value with the same name.
+    pub fn insert<N: Into<String>, V: Into<String>>(&mut self, name: N, value: V) {
+        self.cookies.insert(name.into(), value.into());
+    }
+
+    pub fn get(&self, name: &str) -> Option<&str> {
+        self.cookies.get(name).map(String::as_str)
+    }
+
+    pub fn remove(&mut self, name: &str) -> Option<String> {
+        self.cookies.remove(name)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.cookies.is_empty()
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &str)> {
+        self.cookies
+            .iter()
+            .map(|(name, value)| (name.as_str(), value.as_str()))
+    }
+
+    /// Value of `Cookie` header or `None`, if jar is empty.
+    pub fn to_header(&self) -> Option<String> {
+        if self.is_empty() {
+            return None;
+        }
+        Some(
+            self.iter()
+                .map(|(name, value)| format!("{}={}", name, value))
+                .collect::<Vec<_>>()
+                .join("; "),
+        )
+    }
+}
+
+/// Find header value by case-insensitive name.
+fn find_header<'a>(headers: &'a [(String, String)], name: &str) -> Option<&'a str> {
+    headers
+        .iter()
+        .find(|(header, _)| header.eq_ignore_ascii_case(name))
+        .map(|(_, value)| value.as_str())
+}
+
+/// HTTP request.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Request {
+    pub method: Method,
+    pub url: String,
+    pub headers: Vec<(String, String)>,
+    pub cookies: CookieJar,
+    /// See `Request::unconditional`.
+    pub(crate) unconditional: bool,
+}
+
+impl Request {
+    /// Build GET request without headers and cookies.
+    pub fn get<U: Into<String>>(url: U) -> Self {
+        Self {
+            method: Method::Get,
+            url: url.into(),
+            headers: Vec::new(),
+            cookies: CookieJar::new(),
+            unconditional: false,
+        }
+    }
+
+    /// Ask `ConditionalClient` to send request without validators, so full
+    /// body is returned. Only wrappers see this flag, it is not sent.
+    pub fn unconditional(mut self) -> Self {
+        self.unconditional = true;
+        self
+    }
+
+    pub fn is_unconditional(&self) -> bool {
+        self.unconditional
+    }
+
+    /// Add header, replacing previous value with the same name.
+    pub fn with_header<N: Into<String>, V: Into<String>>(mut self, name: N, value: V) -> Self {
+        let name = name.into();
+        self.headers
+            .retain(|(header, _)| !header.eq_ignore_ascii_case(&name));
+        self.headers.push((name, value.into()));
+        self
+    }
+
+    /// Add cookie.
+    pub fn with_cookie<N: Into<String>, V: Into<String>>(mut self, name: N, value: V) -> Self {
+        self.cookies.insert(name, value);
+        self
+    }
+
+    /// Replace all cookies.
+    pub fn with_cookies(mut self, cookies: CookieJar) -> Self {
+        self.cookies = cookies;
+        self
+    }
+
+    /// Get header value by case-insensitive name.
+    pub fn header(&self, name: &str) -> Option<&str> {
+        find_header(&self.headers, name)
+    }
+}
+
+/// HTTP response.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Response {
+    pub url: String,
+    pub status: u16,
+    pub headers: Vec<(String, String)>,
+    pub body: String,
+}
+
+impl Response {
+    /// Build successful response without headers.
+    pub fn ok<U: Into<String>, B: Into<String>>(url: U, body: B) -> Self {
+        Self {
+            url: url.into(),
+            status: 200,
+            headers: Vec::new(),
+            body: body.into(),
+        }
+    }
+
+    /// Get header value by case-insensitive name.
+    pub fn header(&self, name: &str) -> Option<&str> {
+        find_header(&self.headers, name)
+    }
+
+    pub fn is_success(&self) -> bool {
+        (200..300).contains(&self.status)
+    }
+
+    /// Value of `Retry-After` header, if any.
+    pub fn retry_after(&self) -> Option<Duration> {
+        self.header("Retry-After").and_then(parse_retry_after)
+    }
+
+    /// Get body of successful response or `HttpStatusError` otherwise.
+    pub fn into_body(self) -> Result<String, HttpStatusError> {
+        if self.is_success() {
+            Ok(self.body)
+        } else {
+            Err(HttpStatusError {
+                status: self.status,
+                retry_after: self.retry_after(),
+                url: self.url,
+                body: self.body,
+            })
+        }
+    }
+}
+
+impl From<HttpStatusError> for Response {
+    fn from(err: HttpStatusError) -> Self {
+        let headers = err
+            .retry_after
+            .map(|retry_after| vec![("Retry-After".to_string(), retry_after.as_secs().to_string())])
+            .unwrap_or_default();
+        Self {
+            url: err.url,
+            status: err.status,
+            headers,
+            body: err.body,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn cookie_header() {
+        let mut jar = CookieJar::new();
+        assert_eq!(jar.to_header(), None);
+        jar.insert("timezone", "Europe/Copenhagen");
+        jar.insert("hideSpoilers", "1");
+        assert_eq!(
+            jar.to_header(),
+            Some("hideSpoilers=1; timezone=Europe/Copenhagen".into())
+        );
+    }
+
+    #[test]
+    fn request_headers() {
+        let request = Request::get("https://www.hltv.org/")
+            .with_header("User-Agent", "a")
+            .with_header("user-agent", "b");
+        assert_eq!(request.headers.len(), 1);
+        assert_eq!(request.header("USER-AGENT"), Some("b"));
+    }
+
+    #[test]
+    fn response_into_body() {
+        assert_eq!(Response::ok("/", "body").into_body(), Ok("body".into()));
+
+        let response = Response {
+            url: "/".into(),
+            status: 429,
+            headers: vec![("retry-after".into(), "5".into())],
+            body: "slow down".into(),
+        };
+        let err = response.clone().into_body().unwrap_err();
+        assert_eq!(err.status, 429);
+        assert_eq!(err.retry_after, Some(Duration::from_secs(5)));
+        assert_eq!(Response::from(err).retry_after(), response.retry_after());
+    }
+}
@@ -0,0 +1,352 @@
+use std::{error::Error, io, time::Duration};
+
+use super::{get_via_execute, HttpStatusError, HttpsClient, Request, Response};
+
+/// Retry settings for `RetryClient`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct RetryPolicy {
+    /// Maximal number of attempts, including the first one.
+    pub max_attempts: u32,
+    /// Delay before first retry.
+    pub initial_backoff: Duration,
+    /// Upper bound of delay between attempts.
+    pub max_backoff: Duration,
+    /// Factor, by which delay grows after each attempt.
+    pub multiplier: f64,
+    /// Upper bound of random delay, added to each backoff.
+    pub jitter: Duration,
+    /// Wait for duration from `Retry-After` header, if server provided one.
+    /// If it is longer than `max_backoff`, error is returned without retry.
+    pub respect_retry_after: bool,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
+            max_attempts: 4,
+            initial_backoff: Duration::from_millis(500),
+            max_backoff: Duration::from_secs(30),
+            multiplier: 2.0,
+            jitter: Duration::from_millis(250),
+            respect_retry_after: true,
+        }
+    }
+}
+
+impl RetryPolicy {
+    /// Backoff before retry number `retry` (starting from 0), without jitter.
+    fn backoff(&self, retry: u32) -> Duration {
+        let factor = self.multiplier.max(1.0).powi(retry as i32);
+        let backoff = self.initial_backoff.as_secs_f64() * factor;
+        Duration::from_secs_f64(backoff.min(self.max_backoff.as_secs_f64()))
+    }
+}
+
+/// Check whether HTTP status is transient: 408, 425, 429, 500, 502, 503 or
+/// 504.
+pub fn is_transient_status(status: u16) -> bool {
+    matches!(status, 408 | 425 | 429 | 500 | 502 | 503 | 504)
+}
+
+/// Check whether IO error is caused by timeout or dropped connection.
+pub(crate) fn is_transient_io_kind(kind: io::ErrorKind) -> bool {
+    matches!(
+        kind,
+        io::ErrorKind::ConnectionReset
+            | io::ErrorKind::ConnectionAborted
+            | io::ErrorKind::ConnectionRefused
+            | io::ErrorKind::BrokenPipe
+            | io::ErrorKind::TimedOut
+            | io::ErrorKind::Interrupted
+            | io::ErrorKind::UnexpectedEof
+    )
+}
+
+/// Iterate over error and its sources, including errors wrapped into
+/// `std::io::Error`.
+fn error_chain<'a>(
+    err: &'a (dyn Error + 'static),
+) -> impl Iterator<Item = &'a (dyn Error + 'static)> {
+    std::iter::successors(Some(err), |&err: &&'a (dyn Error + 'static)| {
+        match err.downcast_ref::<io::Error>() {
+            // IO errors are often used as wrappers for other errors
+            Some(io_err) => io_err
+                .get_ref()
+                .map(|err| err as &(dyn Error + 'static))
+                .or_else(|| err.source()),
+            None => err.source(),
+        }
+    })
+}
+
+/// Find kind of IO error in error chain. Transient kinds are preferred over
+/// generic wrappers.
+pub(crate) fn find_io_error_kind(err: &(dyn Error + 'static)) -> Option<io::ErrorKind> {
+    let kinds = error_chain(err)
+        .filter_map(|err| err.downcast_ref::<io::Error>())
+        .map(io::Error::kind)
+        .collect::<Vec<_>>();
+    kinds
+        .iter()
+        .find(|kind| is_transient_io_kind(**kind))
+        .or_else(|| kinds.first())
+        .copied()
+}
+
+/// Check whether error is transient, ie request may succeed if repeated.
+///
+/// Transient errors are `HttpStatusError` with transient status (see
+/// `is_transient_status`), and `std::io::Error` caused by timeouts or dropped
+/// connections. Error sources are checked too.
+pub fn is_transient_error(err: &(dyn Error + 'static)) -> bool {
+    for err in error_chain(err) {
+        if let Some(err) = err.downcast_ref::<HttpStatusError>() {
+            return is_transient_status(err.status);
+        }
+        if err
+            .downcast_ref::<io::Error>()
+            .is_some_and(|err| is_transient_io_kind(err.kind()))
+        {
+            return true;
+        }
+    }
+    false
+}
+
+/// `HttpsClient` wrapper, which retries requests failed with transient
+/// errors (see `is_transient_error`), using exponential backoff with jitter.
+pub struct RetryClient<C: HttpsClient> {
+    client: C,
+    policy: RetryPolicy,
+}
+
+impl<C: HttpsClient> RetryClient<C> {
+    /// Wrap client with default retry policy.
+    pub fn new(client: C) -> Self {
+        Self::with_policy(client, RetryPolicy::default())
+    }
+
+    /// Wrap client with provided retry policy.
+    pub fn with_policy(client: C, policy: RetryPolicy) -> Self {
+        Self { client, policy }
+    }
+
+    /// Get reference to wrapped client.
+    pub fn inner(&self) -> &C {
+        &self.client
+    }
+
+    /// Get delay before next retry or `None`, if failure should not be
+    /// retried.
+    fn delay(
+        &self,
+        transient: bool,
+        retry_after: Option<Duration>,
+        retry: u32,
+    ) -> Option<Duration> {
+        if retry + 1 >= self.policy.max_attempts || !transient {
+            return None;
+        }
+        let backoff = self.policy.backoff(retry);
+        let retry_after = retry_after.filter(|_| self.policy.respect_retry_after);
+        let delay = match retry_after {
+            Some(retry_after) if retry_after > self.policy.max_backoff => return None,
+            Some(retry_after) => retry_after.max(backoff),
+            None => backoff,
+        };
+        Some(delay + self.policy.jitter.mul_f64(rand::random::<f64>()))
+    }
+}
+
+impl<C: HttpsClient> HttpsClient for RetryClient<C> {
+    fn get(&self, url: &str) -> Result<String, Box<dyn Error>> {
+        get_via_execute(self, url)
+    }
+
+    fn execute(&self, request: &Request) -> Result<Response, Box<dyn Error>> {
+        let mut retry = 0;
+        loop {
+            let res = self.client.execute(request);
+            let delay = match &res {
+                Ok(response) if response.is_success() => None,
+                Ok(response) => self.delay(
+                    is_transient_status(response.status),
+                    response.retry_after(),
+                    retry,
+                ),
+                Err(err) => self.delay(
+                    is_transient_error(err.as_ref()),
+                    err.downcast_ref::<HttpStatusError>()
+                        .and_then(|err| err.retry_after),
+                    retry,
+                ),
+            };
+            match delay {
+                Some(delay) => std::thread::sleep(delay),
+                None => return res,
+            }
+            retry += 1;
+        }
+    }
+
+    fn is_conditional(&self) -> bool {
+        self.client.is_conditional()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{cell::RefCell, time::Instant};
+
+    use super::*;
+
+    /// Client, which returns prepared results one by one.
+    struct SequenceClient {
+        results: RefCell<Vec<Result<String, Box<dyn Error>>>>,
+        calls: RefCell<u32>,
+    }
+
+    impl SequenceClient {
+        fn new(mut results: Vec<Result<String, Box<dyn Error>>>) -> Self {
+            results.reverse();
+            Self {
+                results: RefCell::new(results),
+                calls: RefCell::new(0),
+            }
+        }
+    }
+
+    impl HttpsClient for SequenceClient {
+        fn get(&self, _url: &str) -> Result<String, Box<dyn Error>> {
+            *self.calls.borrow_mut() += 1;
+            self.results
+                .borrow_mut()
+                .pop()
+                .unwrap_or_else(|| Ok("last".into()))
+        }
+    }
+
+    fn status(status: u16, retry_after: Option<Duration>) -> Result<String, Box<dyn Error>> {
+        Err(Box::new(HttpStatusError {
+            status,
+            url: "/".into(),
+            retry_after,
+            body: String::new(),
+        }))
+    }
+
+    fn policy(max_attempts: u32) -> RetryPolicy {
+        RetryPolicy {
+            max_attempts,
+            initial_backoff: Duration::from_millis(1),
+            max_backoff: Duration::from_millis(100),
+            multiplier: 2.0,
+            jitter: Duration::from_secs(0),
+            respect_retry_after: true,
+        }
+    }
+
+    #[test]
+    fn retry_transient() {
+        let client = RetryClient::with_policy(
+            SequenceClient::new(vec![
+                status(503, None),
+                Err(Box::new(io::Error::from(io::ErrorKind::ConnectionReset))),
+                Ok("ok".into()),
+            ]),
+            policy(3),
+        );
+        assert_eq!(client.get("/").unwrap(), "ok");
+        assert_eq!(*client.inner().calls.borrow(), 3);
+    }
+
+    #[test]
+    fn no_retry_permanent() {
+        let client =
+            RetryClient::with_policy(SequenceClient::new(vec![status(404, None)]), policy(3));
+        assert!(client.get("/").is_err());
+        assert_eq!(*client.inner().calls.borrow(), 1);
+    }
+
+    #[test]
+    fn max_attempts() {
+        let client = RetryClient::with_policy(
+            SequenceClient::new(vec![
+                status(429, None),
+                status(429, None),
+                status(429, None),
+            ]),
+            policy(2),
+        );
+        assert!(client.get("/").is_err());
+        assert_eq!(*client.inner().calls.borrow(), 2);
+    }
+
+    #[test]
+    fn retry_after() {
+        // `Retry-After` header has seconds precision
+        let client = RetryClient::with_policy(
+            SequenceClient::new(vec![status(429, Some(Duration::from_secs(1)))]),
+            RetryPolicy {
+                max_backoff: Duration::from_secs(2),
+                ..policy(2)
+            },
+        );
+        let start = Instant::now();
+        assert_eq!(client.get("/").unwrap(), "last");
+        assert!(start.elapsed() >= Duration::from_secs(1));
+    }
+
+    #[test]
+    fn retry_after_too_long() {
+        let client = RetryClient::with_policy(
+            SequenceClient::new(vec![status(503, Some(Duration::from_secs(3600)))]),
+            policy(2),
+        );
+        assert!(client.get("/").is_err());
+        assert_eq!(*client.inner().calls.borrow(), 1);
+    }
+
+    #[test]
+    fn retry_response_status() {
+        struct StatusClient(RefCell<u16>);
+
+        impl HttpsClient for StatusClient {
+            fn get(&self, url: &str) -> Result<String, Box<dyn Error>> {
+                get_via_execute(self, url)
+            }
+
+            fn execute(&self, request: &Request) -> Result<Response, Box<dyn Error>> {
+                let status = self.0.replace(200);
+                Ok(Response {
+                    status,
+                    ..Response::ok(request.url.as_str(), "")
+                })
+            }
+        }
+
+        let client = RetryClient::with_policy(StatusClient(RefCell::new(502)), policy(2));
+        assert_eq!(client.execute(&Request::get("/")).unwrap().status, 200);
+
+        let client = RetryClient::with_policy(StatusClient(RefCell::new(403)), policy(2));
+        assert_eq!(client.execute(&Request::get("/")).unwrap().status, 403);
+    }
+
+    #[test]
+    fn backoff_growth() {
+        let policy = policy(10);
+        assert_eq!(policy.backoff(0), Duration::from_millis(1));
+        assert_eq!(policy.backoff(3), Duration::from_millis(8));
+        assert_eq!(policy.backoff(9), Duration::from_millis(100));
+    }
+
+    #[test]
+    fn transient_classification() {
+        let wrapped = io::Error::other(io::Error::from(io::ErrorKind::TimedOut));
+        assert!(is_transient_error(&wrapped));
+        assert!(!is_transient_error(&io::Error::from(
+            io::ErrorKind::InvalidData
+        )));
+        assert!(!is_transient_error(&std::fmt::Error));
+    }
+}
@@ -0,0 +1,183 @@
+use std::{
+    error::Error,
+    sync::Mutex,
+    time::{Duration, Instant},
+};
+
+use super::{get_via_execute, HttpsClient, Request, Response};
+
+/// Throttling settings for `ThrottledClient`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ThrottleConfig {
+    /// Minimal interval between requests once burst is exhausted.
+    pub min_interval: Duration,
+    /// Upper bound of random delay, added before each throttled request.
+    pub jitter: Duration,
+    /// Number of requests, which can be sent without waiting.
+    pub burst: u32,
+}
+
+impl Default for ThrottleConfig {
+    fn default() -> Self {
+        Self {
+            min_interval: Duration::from_secs(1),
+            jitter: Duration::from_millis(250),
+            burst: 3,
+        }
+    }
+}
+
+/// Token bucket state.
+struct Bucket {
+    tokens: f64,
+    last_refill: Instant,
+}
+
+impl Bucket {
+    fn refill(&mut self, config: &ThrottleConfig) {
+        let now = Instant::now();
+        let capacity = f64::from(config.burst.max(1));
+        if config.min_interval == Duration::from_secs(0) {
+            self.tokens = capacity;
+        } else {
+            let elapsed = now.duration_since(self.last_refill);
+            self.tokens = (self.tokens + elapsed.as_secs_f64() / config.min_interval.as_secs_f64())
+                .min(capacity);
+        }
+        self.last_refill = now;
+    }
+}
+
+/// `HttpsClient` wrapper, which limits rate of requests to protect from
+/// Cloudflare bans.
+///
+/// Requests are limited by token bucket: up to `burst` requests are sent
+/// immediately, after that one request per `min_interval`. Each delayed
+/// request additionally waits random time up to `jitter`.
+pub struct ThrottledClient<C: HttpsClient> {
+    client: C,
+    config: ThrottleConfig,
+    bucket: Mutex<Bucket>,
+}
+
+impl<C: HttpsClient> ThrottledClient<C> {
+    /// Wrap client with default throttling settings.
+    pub fn new(client: C) -> Self {
+        Self::with_config(client, ThrottleConfig::default())
+    }
+
+    /// Wrap client with provided throttling settings.
+    pub fn with_config(client: C, config: ThrottleConfig) -> Self {
+        let bucket = Bucket {
+            tokens: f64::from(config.burst.max(1)),
+            last_refill: Instant::now(),
+        };
+        Self {
+            client,
+            config,
+            bucket: Mutex::new(bucket),
+        }
+    }
+
+    /// Get reference to wrapped client.
+    pub fn inner(&self) -> &C {
+        &self.client
+    }
+
+    /// Wait until request can be sent.
+    fn acquire(&self) {
+        // Lock is held while sleeping, so concurrent requests are queued
+        let mut bucket = self.bucket.lock().unwrap_or_else(|err| err.into_inner());
+        bucket.refill(&self.config);
+        if bucket.tokens < 1.0 {
+            let wait = self.config.min_interval.mul_f64(1.0 - bucket.tokens)
+                + self.config.jitter.mul_f64(rand::random::<f64>());
+            std::thread::sleep(wait);
+            bucket.refill(&self.config);
+        }
+        bucket.tokens = (bucket.tokens - 1.0).max(0.0);
+    }
+}
+
+impl<C: HttpsClient> HttpsClient for ThrottledClient<C> {
+    fn get(&self, url: &str) -> Result<String, Box<dyn Error>> {
+        get_via_execute(self, url)
+    }
+
+    fn execute(&self, request: &Request) -> Result<Response, Box<dyn Error>> {
+        self.acquire();
+        self.client.execute(request)
+    }
+
+    fn is_conditional(&self) -> bool {
+        self.client.is_conditional()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct NoneHttpsClient;
+
+    impl HttpsClient for NoneHttpsClient {
+        fn get(&self, _url: &str) -> Result<String, Box<dyn Error>> {
+            Ok(String::new())
+        }
+    }
+
+    fn config(min_interval_ms: u64, burst: u32) -> ThrottleConfig {
+        ThrottleConfig {
+            min_interval: Duration::from_millis(min_interval_ms),
+            jitter: Duration::from_secs(0),
+            burst,
+        }
+    }
+
+    #[test]
+    fn burst_is_not_throttled() {
+        let client = ThrottledClient::with_config(NoneHttpsClient, config(1000, 3));
+        let start = Instant::now();
+        for _ in 0..3 {
+            client.get("/").unwrap();
+        }
+        assert!(start.elapsed() < Duration::from_millis(500));
+    }
+
+    #[test]
+    fn throttled_after_burst() {
+        let client = ThrottledClient::with_config(NoneHttpsClient, config(50, 2));
+        let start = Instant::now();
+        for _ in 0..5 {
+            client.get("/").unwrap();
+        }
+        assert!(start.elapsed() >= Duration::from_millis(150));
+    }
+
+    #[test]
+    fn jitter_adds_delay() {
+        let client = ThrottledClient::with_config(
+            NoneHttpsClient,
+            ThrottleConfig {
+                jitter: Duration::from_millis(100),
+                ..config(10, 1)
+            },
+        );
+        let start = Instant::now();
+        for _ in 0..3 {
+            client.get("/").unwrap();
+        }
+        assert!(start.elapsed() >= Duration::from_millis(20));
+        assert!(start.elapsed() < Duration::from_millis(1000));
+    }
+
+    #[test]
+    fn zero_interval() {
+        let client = ThrottledClient::with_config(NoneHttpsClient, config(0, 0));
+        let start = Instant::now();
+        for _ in 0..100 {
+            client.get("/").unwrap();
+        }
+        assert!(start.elapsed() < Duration::from_millis(500));
+    }
+}
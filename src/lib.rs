@@ -1,35 +1,247 @@
+#[cfg(feature = "attohttpc_client")]
+pub use crate::https_client::impls::attohttpc_impl::{AttoHttpcConfig, AttoHttpcImpl};
+#[cfg(feature = "reqwest_client")]
+pub use crate::https_client::impls::reqwest_impl::{AsyncReqwestImpl, ReqwestConfig, ReqwestImpl};
+#[cfg(feature = "ureq_client")]
+pub use crate::https_client::impls::ureq_impl::{UreqConfig, UreqImpl};
+#[cfg(feature = "testing")]
+pub use crate::https_client::{MockClient, MockResponse};
 pub use crate::{
-    api::HltvApi,
-    https_client::{impls::attohttpc_impl::AttoHttpcImpl, HttpsClient},
+    api::{
+        ArticleBrief, AsyncHltvApi, EntityId, EventId, HltvApi, HltvApiBuilder, HltvPage,
+        MainPageArticleBriefs, Map, MatchFormat, MatchId, MatchResult, MatchesResults, Month,
+        NewsId, PageKind, ParsedCacheConfig, PlayerId, TeamId, UpcomingMatch, UpcomingMatchTeam,
+        UpcomingMatchTeams, UpcomingMatches,
+    },
+    https_client::{
+        detect_block, get_via_execute, is_transient_error, is_transient_status, parse_retry_after,
+        AsyncHttpsClient, BlockKind, CacheTtl, CachingClient, CircuitBreakerClient,
+        CircuitBreakerConfig, CircuitBreakerError, CircuitState, ConditionalClient, CookieJar,
+        HttpStatusError, HttpsClient, LimitError, Method, NoProxyAvailableError, Proxy, ProxyError,
+        ProxyHealth, ProxyPoolClient, ProxyPoolConfig, ProxyScheme, RecordingClient, ReplayClient,
+        Request, RequestBudget, Response, RetryClient, RetryPolicy, ThrottleConfig,
+        ThrottledClient, DEFAULT_MAX_BODY_SIZE, DEFAULT_MAX_REDIRECTS, DEFAULT_USER_AGENT,
+        NOT_MODIFIED,
+    },
 };
+/// Date and time types, used in parsed data.
+pub use chrono;
+
+#[macro_use]
+mod trace;
 
 mod api;
 mod https_client;
 
+use std::{io, time::Duration};
+
+use crate::https_client::{find_io_error_kind, is_transient_io_kind};
+
 /// Default HLTV URL.
-pub const HLTV_URL: &'static str = "https://www.hltv.org";
+pub const HLTV_URL: &str = "https://www.hltv.org";
 
 pub type Result<T> = std::result::Result<T, Error>;
 
 /// General error type for this crate.
 #[derive(Debug, thiserror::Error)]
 pub enum Error {
-    #[error("HTTPS lib error: {0}")]
-    HttpsClient(#[source] Box<dyn std::error::Error>),
+    #[error("HTTP status {status} for {url}")]
+    HttpStatus {
+        status: u16,
+        url: String,
+        /// Value of `Retry-After` header, if any.
+        retry_after: Option<Duration>,
+    },
+
+    #[error("Request to {url} timed out")]
+    Timeout { url: String },
+
+    /// Connection, TLS or other client failure.
+    #[error("Request to {url} failed: {message}")]
+    Transport {
+        url: String,
+        message: String,
+        /// Kind of underlying IO error, if any.
+        io_kind: Option<io::ErrorKind>,
+    },
 
     #[error("IO error: {0}")]
-    Io(#[from] std::io::Error),
+    Io(#[from] io::Error),
 
     #[error("CSS parse error: {0}")]
     CssParse(String),
 
     #[error("HLTV parse error: {0}")]
     HltvParse(String),
+
+    #[error("Blocked by Cloudflare ({kind:?}), retry after {retry_after:?}")]
+    Blocked {
+        kind: BlockKind,
+        /// Value of `Retry-After` header, if any.
+        retry_after: Option<Duration>,
+    },
+
+    #[error("Response from {url} is larger than {limit} bytes")]
+    BodyTooLarge { url: String, limit: u64 },
+
+    #[error("Request to {url} is redirected more than {limit} times")]
+    TooManyRedirects { url: String, limit: u32 },
+
+    /// Request was not sent by `CircuitBreakerClient`.
+    #[error("Request to {url} was not sent: {source}")]
+    CircuitOpen {
+        url: String,
+        source: CircuitBreakerError,
+    },
+
+    /// All proxies of `ProxyPoolClient` are cooling down.
+    #[error("No proxy is available for {url}, retry after {retry_after:?}")]
+    NoProxyAvailable { url: String, retry_after: Duration },
+
+    /// Inconsistent `HltvApiBuilder` settings.
+    #[error("Invalid HltvApi configuration: {0}")]
+    InvalidConfig(String),
 }
 
+impl Error {
+    /// Build error from error of `HttpsClient` for request to `url`.
+    pub fn from_client_error(err: &(dyn std::error::Error + 'static), url: &str) -> Self {
+        if let Some(err) = err.downcast_ref::<HttpStatusError>() {
+            return Self::HttpStatus {
+                status: err.status,
+                url: err.url.clone(),
+                retry_after: err.retry_after,
+            };
+        }
+        match err.downcast_ref::<LimitError>() {
+            Some(LimitError::BodyTooLarge { limit }) => {
+                return Self::BodyTooLarge {
+                    url: url.into(),
+                    limit: *limit,
+                }
+            }
+            Some(LimitError::TooManyRedirects { limit }) => {
+                return Self::TooManyRedirects {
+                    url: url.into(),
+                    limit: *limit,
+                }
+            }
+            None => {}
+        }
+        if let Some(err) = err.downcast_ref::<NoProxyAvailableError>() {
+            return Self::NoProxyAvailable {
+                url: url.into(),
+                retry_after: err.retry_after,
+            };
+        }
+        if let Some(err) = err.downcast_ref::<CircuitBreakerError>() {
+            return Self::CircuitOpen {
+                url: url.into(),
+                source: err.clone(),
+            };
+        }
+        match find_io_error_kind(err) {
+            Some(io::ErrorKind::TimedOut) => Self::Timeout { url: url.into() },
+            io_kind => Self::Transport {
+                url: url.into(),
+                message: err.to_string(),
+                io_kind,
+            },
+        }
+    }
+
+    /// Check whether request may succeed if repeated: transient HTTP
+    /// statuses (see `is_transient_status`), timeouts, dropped connections
+    /// and Cloudflare rate limits.
+    pub fn is_retryable(&self) -> bool {
+        match self {
+            Self::HttpStatus { status, .. } => is_transient_status(*status),
+            Self::Timeout { .. } => true,
+            Self::Transport { io_kind, .. } => io_kind.is_some_and(is_transient_io_kind),
+            Self::Io(err) => is_transient_io_kind(err.kind()),
+            Self::Blocked { kind, .. } => *kind == BlockKind::RateLimited,
+            Self::NoProxyAvailable { .. } => true,
+            Self::CssParse(_)
+            | Self::HltvParse(_)
+            | Self::InvalidConfig(_)
+            | Self::BodyTooLarge { .. }
+            | Self::TooManyRedirects { .. }
+            | Self::CircuitOpen { .. } => false,
+        }
+    }
+}
+
+/// Errors are equal, if they have the same variant and fields. IO errors are
+/// compared by kind.
 impl PartialEq for Error {
     fn eq(&self, other: &Self) -> bool {
-        self.to_string() == other.to_string()
+        match (self, other) {
+            (
+                Self::HttpStatus {
+                    status,
+                    url,
+                    retry_after,
+                },
+                Self::HttpStatus {
+                    status: other_status,
+                    url: other_url,
+                    retry_after: other_retry_after,
+                },
+            ) => status == other_status && url == other_url && retry_after == other_retry_after,
+            (Self::Timeout { url }, Self::Timeout { url: other_url }) => url == other_url,
+            (
+                Self::Transport {
+                    url,
+                    message,
+                    io_kind,
+                },
+                Self::Transport {
+                    url: other_url,
+                    message: other_message,
+                    io_kind: other_io_kind,
+                },
+            ) => url == other_url && message == other_message && io_kind == other_io_kind,
+            (Self::Io(err), Self::Io(other_err)) => err.kind() == other_err.kind(),
+            (Self::CssParse(text), Self::CssParse(other_text))
+            | (Self::HltvParse(text), Self::HltvParse(other_text))
+            | (Self::InvalidConfig(text), Self::InvalidConfig(other_text)) => text == other_text,
+            (
+                Self::Blocked { kind, retry_after },
+                Self::Blocked {
+                    kind: other_kind,
+                    retry_after: other_retry_after,
+                },
+            ) => kind == other_kind && retry_after == other_retry_after,
+            (
+                Self::BodyTooLarge { url, limit },
+                Self::BodyTooLarge {
+                    url: other_url,
+                    limit: other_limit,
+                },
+            ) => url == other_url && limit == other_limit,
+            (
+                Self::TooManyRedirects { url, limit },
+                Self::TooManyRedirects {
+                    url: other_url,
+                    limit: other_limit,
+                },
+            ) => url == other_url && limit == other_limit,
+            (
+                Self::CircuitOpen { url, source },
+                Self::CircuitOpen {
+                    url: other_url,
+                    source: other_source,
+                },
+            ) => url == other_url && source == other_source,
+            (
+                Self::NoProxyAvailable { url, retry_after },
+                Self::NoProxyAvailable {
+                    url: other_url,
+                    retry_after: other_retry_after,
+                },
+            ) => url == other_url && retry_after == other_retry_after,
+            _ => false,
+        }
     }
 }
 
@@ -39,18 +251,130 @@ impl<'i, E: 'i + std::fmt::Debug> From<cssparser::ParseError<'i, E>> for Error {
     }
 }
 
-/// Convert `Option::None` to this crate `Error::String`.
+/// Convert `Option::None` to this crate `Error::HltvParse`.
 trait NoneErrorExt<T> {
-    fn css_err<E: Into<String>>(self, c: E) -> Result<T>;
     fn hltv_parse_err<E: Into<String>>(self, c: E) -> Result<T>;
 }
 
 impl<T> NoneErrorExt<T> for Option<T> {
-    fn css_err<E: Into<String>>(self, text: E) -> Result<T> {
-        self.ok_or_else(|| Error::CssParse(text.into()))
-    }
-
     fn hltv_parse_err<E: Into<String>>(self, text: E) -> Result<T> {
         self.ok_or_else(|| Error::HltvParse(text.into()))
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn error_is_send_sync() {
+        fn assert_send_sync<T: Send + Sync + 'static>() {}
+        assert_send_sync::<Error>();
+    }
+
+    #[test]
+    fn from_client_error() {
+        let url = "https://www.hltv.org/matches";
+
+        let err = HttpStatusError {
+            status: 404,
+            url: url.into(),
+            retry_after: None,
+            body: String::new(),
+        };
+        assert_eq!(
+            Error::from_client_error(&err, url),
+            Error::HttpStatus {
+                status: 404,
+                url: url.into(),
+                retry_after: None
+            }
+        );
+
+        let err = io::Error::other(io::Error::from(io::ErrorKind::TimedOut));
+        assert_eq!(
+            Error::from_client_error(&err, url),
+            Error::Timeout { url: url.into() }
+        );
+
+        let err = io::Error::from(io::ErrorKind::ConnectionRefused);
+        assert!(matches!(
+            Error::from_client_error(&err, url),
+            Error::Transport {
+                io_kind: Some(io::ErrorKind::ConnectionRefused),
+                ..
+            }
+        ));
+
+        let err = LimitError::BodyTooLarge { limit: 1024 };
+        assert_eq!(
+            Error::from_client_error(&err, url),
+            Error::BodyTooLarge {
+                url: url.into(),
+                limit: 1024
+            }
+        );
+
+        let err = CircuitBreakerError::Open {
+            retry_after: Duration::from_secs(1),
+        };
+        assert_eq!(
+            Error::from_client_error(&err, url),
+            Error::CircuitOpen {
+                url: url.into(),
+                source: err.clone()
+            }
+        );
+
+        let err = NoProxyAvailableError {
+            retry_after: Duration::from_secs(5),
+        };
+        let err = Error::from_client_error(&err, url);
+        assert_eq!(
+            err,
+            Error::NoProxyAvailable {
+                url: url.into(),
+                retry_after: Duration::from_secs(5)
+            }
+        );
+        assert!(err.is_retryable());
+
+        let err = ProxyError::InvalidUrl("proxy".into());
+        assert!(matches!(
+            Error::from_client_error(&err, url),
+            Error::Transport { io_kind: None, .. }
+        ));
+    }
+
+    #[test]
+    fn retryable() {
+        let status = |status| Error::HttpStatus {
+            status,
+            url: "/".into(),
+            retry_after: None,
+        };
+        assert!(status(503).is_retryable());
+        assert!(!status(404).is_retryable());
+        assert!(Error::Timeout { url: "/".into() }.is_retryable());
+        assert!(Error::Blocked {
+            kind: BlockKind::RateLimited,
+            retry_after: None
+        }
+        .is_retryable());
+        assert!(!Error::Blocked {
+            kind: BlockKind::Captcha,
+            retry_after: None
+        }
+        .is_retryable());
+        assert!(!Error::HltvParse("No team1".into()).is_retryable());
+    }
+
+    #[test]
+    fn error_eq() {
+        assert_eq!(
+            Error::Io(io::Error::new(io::ErrorKind::NotFound, "a")),
+            Error::Io(io::Error::new(io::ErrorKind::NotFound, "b"))
+        );
+        assert_ne!(Error::HltvParse("a".into()), Error::CssParse("a".into()));
+    }
+}
@@ -0,0 +1,163 @@
+//! Helpers for optional `tracing` instrumentation. Without `tracing` feature
+//! they do nothing.
+
+use std::{fmt::Display, time::Instant};
+
+use crate::{Error, Response, Result};
+
+/// Enter span, which lasts until the end of current block.
+macro_rules! enter_span {
+    ($($span:tt)+) => {
+        #[cfg(feature = "tracing")]
+        let _span = tracing::info_span!($($span)+).entered();
+    };
+}
+
+/// Instrument future with span.
+macro_rules! instrumented {
+    ($future:expr, $($span:tt)+) => {{
+        #[cfg(feature = "tracing")]
+        let future = tracing::Instrument::instrument($future, tracing::info_span!($($span)+));
+        #[cfg(not(feature = "tracing"))]
+        let future = $future;
+        future
+    }};
+}
+
+/// Record status, body size and latency of HTTP request in current span
+/// (fields `status`, `bytes` and `latency_ms`).
+#[cfg(feature = "tracing")]
+pub(crate) fn record_response<E: Display>(res: &std::result::Result<Response, E>, start: Instant) {
+    let span = tracing::Span::current();
+    span.record("latency_ms", start.elapsed().as_millis() as u64);
+    match res {
+        Ok(response) => {
+            span.record("status", response.status);
+            span.record("bytes", response.body.len() as u64);
+        }
+        Err(err) => tracing::warn!(error = %err, "HTTP request failed"),
+    }
+}
+
+#[cfg(not(feature = "tracing"))]
+pub(crate) fn record_response<E: Display>(
+    _res: &std::result::Result<Response, E>,
+    _start: Instant,
+) {
+}
+
+/// Record number of parsed items and whether page failed to parse in current
+/// span (fields `items_parsed` and `parse_failed`) and log parse failure.
+/// Parsing stops at first invalid item, so failed items are not counted.
+#[cfg(feature = "tracing")]
+pub(crate) fn record_parsed<T>(res: &Result<T>, count: impl FnOnce(&T) -> usize) {
+    let span = tracing::Span::current();
+    match res {
+        Ok(value) => {
+            span.record("items_parsed", count(value) as u64);
+            span.record("parse_failed", false);
+        }
+        Err(err) => {
+            span.record("parse_failed", true);
+            tracing::warn!(error = %err, "Failed to parse page");
+        }
+    }
+}
+
+#[cfg(not(feature = "tracing"))]
+pub(crate) fn record_parsed<T>(_res: &Result<T>, _count: impl FnOnce(&T) -> usize) {}
+
+/// Log failure to parse list item.
+#[cfg(feature = "tracing")]
+pub(crate) fn item_failed(section: &str, index: usize, err: &Error) {
+    tracing::warn!(section, index, error = %err, "Failed to parse item");
+}
+
+#[cfg(not(feature = "tracing"))]
+pub(crate) fn item_failed(_section: &str, _index: usize, _err: &Error) {}
+
+#[cfg(all(test, feature = "tracing"))]
+mod tests {
+    use std::sync::{Arc, Mutex};
+
+    use tracing::{
+        field::{Field, Visit},
+        span, Event, Metadata, Subscriber,
+    };
+
+    use crate::api::archived_article_briefs_from_html;
+
+    /// Subscriber, which records span names and recorded fields. Supports
+    /// single span.
+    #[derive(Clone, Default)]
+    struct Recorder(
+        Arc<Mutex<Vec<String>>>,
+        Arc<Mutex<Option<&'static Metadata<'static>>>>,
+    );
+
+    impl Visit for Recorder {
+        fn record_debug(&mut self, field: &Field, value: &dyn std::fmt::Debug) {
+            self.0
+                .lock()
+                .unwrap()
+                .push(format!("{}={:?}", field.name(), value));
+        }
+    }
+
+    impl Subscriber for Recorder {
+        fn enabled(&self, _metadata: &Metadata<'_>) -> bool {
+            true
+        }
+
+        fn new_span(&self, span: &span::Attributes<'_>) -> span::Id {
+            self.0.lock().unwrap().push(span.metadata().name().into());
+            *self.1.lock().unwrap() = Some(span.metadata());
+            span.record(&mut self.clone());
+            span::Id::from_u64(1)
+        }
+
+        fn record(&self, _span: &span::Id, values: &span::Record<'_>) {
+            values.record(&mut self.clone());
+        }
+
+        fn record_follows_from(&self, _span: &span::Id, _follows: &span::Id) {}
+
+        fn event(&self, event: &Event<'_>) {
+            event.record(&mut self.clone());
+        }
+
+        fn current_span(&self) -> tracing_core::span::Current {
+            match *self.1.lock().unwrap() {
+                Some(metadata) => tracing_core::span::Current::new(span::Id::from_u64(1), metadata),
+                None => tracing_core::span::Current::none(),
+            }
+        }
+
+        fn enter(&self, _span: &span::Id) {}
+
+        fn exit(&self, _span: &span::Id) {}
+    }
+
+    #[test]
+    fn parse_span() {
+        let recorder = Recorder::default();
+        let html = scraper::Html::parse_document(
+            r#"
+<h2 class="newsheader">November 2020</h2>
+<div class="standard-box">
+  <a href="/news/1/first"><div class="newstext">First</div><div class="newstc"><div class="newsrecent">1 hour ago</div><div>1 comment</div></div></a>
+  <a href="/news/2/second"><div class="newstext">Second</div></a>
+</div>
+"#,
+        );
+        tracing::subscriber::with_default(recorder.clone(), || {
+            assert!(archived_article_briefs_from_html(&html).is_err());
+        });
+
+        let records = recorder.0.lock().unwrap();
+        assert_eq!(records[0], "from_html");
+        assert!(records.contains(&"parse_failed=true".to_string()));
+        assert!(records.contains(&"index=1".to_string()));
+        assert!(records.contains(&"section=\"archived news\"".to_string()));
+    }
+}